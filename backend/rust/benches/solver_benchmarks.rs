@@ -0,0 +1,48 @@
+//! Solve-time benchmarks over canned graphs of varying size/density, so
+//! pruning and preprocessing changes to the solver can be validated
+//! quantitatively instead of by feel.
+//!
+//! No `criterion` here (see the comment on `[[bench]]` in `Cargo.toml`):
+//! this is a plain `harness = false` binary that times each case with
+//! `Instant` and prints the results. Run with `cargo bench`.
+
+use rust_maze_solver::bench_support::{generate_component, solve_longest_path};
+use std::time::Instant;
+
+/// Checked-in fixture sizes, from a handful of cells up to a few hundred.
+/// `radius` is the hex grid radius passed to `generate_component`; cell
+/// count grows as `3 * radius^2 + 3 * radius + 1`.
+const FIXTURES: &[(&str, u32)] = &[("tiny", 2), ("small", 4), ("medium", 6), ("large", 9)];
+
+/// Every seed carves a differently-shaped perfect maze (a tree, so density
+/// is fixed by construction) over the same radius, standing in for the
+/// "property-generated" fixtures alongside the checked-in sizes above.
+const SEEDS: &[u64] = &[1, 2, 3];
+
+const STRATEGIES: &[(&str, Option<&str>)] = &[("exact-backtracking", None), ("beam-search", Some("beam-search"))];
+
+/// Names accepted by `neighbor_ordering_by_name`; `None` is today's default
+/// (`ClockwiseNumeric`). Measures whether `sort_neighbors_clockwise`'s
+/// row-major-numeric assumption is actually pulling its weight versus the
+/// alternatives it was generalized alongside.
+const ORDERINGS: &[(&str, Option<&str>)] = &[
+    ("clockwise-numeric", None),
+    ("degree-ascending", Some("degree-ascending")),
+    ("random", Some("random")),
+];
+
+fn main() {
+    for &(label, radius) in FIXTURES {
+        for &seed in SEEDS {
+            let component = generate_component(radius, seed);
+            println!("== {label} (radius {radius}, seed {seed}, {} cells) ==", component.len());
+            for &(strategy_label, strategy) in STRATEGIES {
+                for &(ordering_label, ordering) in ORDERINGS {
+                    let start = Instant::now();
+                    let path_len = solve_longest_path(&component, strategy, ordering);
+                    println!("  {strategy_label} / {ordering_label}: {:?} for a {path_len}-node path", start.elapsed());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Pure-Rust entry points for `benches/solver_benchmarks.rs`. Never
+//! registered on the PyO3 module in `lib.rs` — this exists purely so the
+//! benchmark binary can generate fixtures and drive the solver directly,
+//! without going through Python or the `MazeData` JSON payload shape.
+
+use crate::maze_gen::carve_recursive_backtracker;
+use crate::rust_maze_solver::{bench_solve_longest_path, neighbor_ordering_by_name};
+use std::collections::HashMap;
+
+/// Generates a single connected component (a perfect hex maze of the given
+/// `radius`) for use as a benchmark fixture. Seeded for reproducibility so
+/// benchmark runs are comparable across commits.
+pub fn generate_component(radius: u32, seed: u64) -> HashMap<String, Vec<String>> {
+    carve_recursive_backtracker(radius, seed)
+}
+
+/// Runs the longest-path search for `strategy` (`None` for the default
+/// `ExactBacktracking`, or a name accepted by `strategy_by_name`, e.g.
+/// `Some("beam-search")`) over `component`, returning the resulting path
+/// length as a cheap proxy for search effort. `ordering` is a name accepted
+/// by `neighbor_ordering_by_name` (e.g. `Some("degree-ascending")`); `None`
+/// keeps today's default `ClockwiseNumeric` behavior. `NeighborOrdering`
+/// itself is `pub(crate)`, so it's resolved from `ordering` here rather than
+/// taken as a parameter, keeping this function's signature usable from the
+/// separately-compiled `benches/solver_benchmarks.rs` binary.
+pub fn solve_longest_path(component: &HashMap<String, Vec<String>>, strategy: Option<&str>, ordering: Option<&str>) -> usize {
+    bench_solve_longest_path(component, strategy, neighbor_ordering_by_name(ordering, None))
+}
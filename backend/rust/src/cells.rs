@@ -0,0 +1,156 @@
+use crate::maze_gen::AXIAL_DIRECTIONS;
+use crate::rust_maze_solver::{process_and_solve_maze, CancelHandle};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One cell of the renderer's hex grid, in cube/axial coordinates
+/// (`q + r + s == 0`) with a wall flag. Mirrors the frontend's `MazeCell`
+/// shape so the solver can be driven straight from a generated or
+/// hand-edited grid without the Python layer computing adjacency first.
+#[derive(Deserialize)]
+struct MazeCell {
+    q: i32,
+    r: i32,
+    #[serde(rename = "isWall")]
+    is_wall: bool,
+}
+
+#[derive(Serialize)]
+struct CellsMazeData {
+    components: Vec<HashMap<String, Vec<String>>>,
+}
+
+/// Flood-fills the open (non-wall) cells into adjacency components over
+/// their axial neighbors, one component per connected region, assigning
+/// each cell a stable id equal to its index in `cells` so results map
+/// straight back onto the original grid.
+fn cells_to_components(cells: &[MazeCell]) -> Vec<HashMap<String, Vec<String>>> {
+    let mut index_of: HashMap<(i32, i32), usize> = HashMap::new();
+    for (index, cell) in cells.iter().enumerate() {
+        if !cell.is_wall {
+            index_of.insert((cell.q, cell.r), index);
+        }
+    }
+
+    let mut visited = vec![false; cells.len()];
+    let mut components = Vec::new();
+
+    for start in 0..cells.len() {
+        if cells[start].is_wall || visited[start] {
+            continue;
+        }
+
+        let mut adjacency: HashMap<String, Vec<String>> = HashMap::new();
+        let mut stack = vec![start];
+        visited[start] = true;
+
+        while let Some(index) = stack.pop() {
+            let (q, r) = (cells[index].q, cells[index].r);
+            let neighbors: Vec<usize> = AXIAL_DIRECTIONS
+                .iter()
+                .filter_map(|(dq, dr)| index_of.get(&(q + dq, r + dr)))
+                .copied()
+                .collect();
+
+            let entry = adjacency.entry(index.to_string()).or_default();
+            for &neighbor in &neighbors {
+                entry.push(neighbor.to_string());
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        components.push(adjacency);
+    }
+
+    components
+}
+
+/// Solves a hex maze described directly as a flat list of cells in the
+/// renderer's `q,r,s,isWall` shape, deriving adjacency and connected
+/// components in Rust and delegating to `process_and_solve_maze` for the
+/// actual search — so the Python layer no longer has to compute adjacency
+/// itself before every solve.
+#[pyfunction]
+#[pyo3(signature = (cells_json, simplify_waypoints=false, cancel=None, trace_every=None, check_hamiltonian=false, use_cache=true, shortest_path=false, max_path_depth=None, search_strategy=None, lenient=false, cycle=false, deterministic=false, component_timeout_ms=None, compact_indices=false, neighbor_ordering=None, neighbor_ordering_seed=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn solve_from_cells(
+    py: Python,
+    cells_json: String,
+    simplify_waypoints: bool,
+    cancel: Option<CancelHandle>,
+    trace_every: Option<usize>,
+    check_hamiltonian: bool,
+    use_cache: bool,
+    shortest_path: bool,
+    max_path_depth: Option<usize>,
+    search_strategy: Option<String>,
+    lenient: bool,
+    cycle: bool,
+    deterministic: bool,
+    component_timeout_ms: Option<u64>,
+    compact_indices: bool,
+    neighbor_ordering: Option<String>,
+    neighbor_ordering_seed: Option<u64>,
+) -> PyResult<PyObject> {
+    let cells: Vec<MazeCell> = serde_json::from_str(&cells_json)
+        .map_err(|e| PyErr::new::<PyValueError, _>(format!("JSON error: {}", e)))?;
+
+    let data = CellsMazeData { components: cells_to_components(&cells) };
+    let data_str = serde_json::to_string(&data)
+        .map_err(|e| PyErr::new::<PyValueError, _>(format!("serialize error: {}", e)))?;
+
+    process_and_solve_maze(
+        py,
+        data_str.into_pyobject(py)?.into_any().unbind(),
+        simplify_waypoints,
+        cancel,
+        trace_every,
+        check_hamiltonian,
+        use_cache,
+        shortest_path,
+        max_path_depth,
+        search_strategy,
+        lenient,
+        cycle,
+        deterministic,
+        component_timeout_ms,
+        compact_indices,
+        neighbor_ordering,
+        neighbor_ordering_seed,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cells_to_components_skips_walls_and_splits_regions() {
+        // Two isolated open cells (no shared axial neighbor) plus one wall.
+        let cells = vec![
+            MazeCell { q: 0, r: 0, is_wall: false },
+            MazeCell { q: 0, r: 1, is_wall: true },
+            MazeCell { q: 5, r: 5, is_wall: false },
+        ];
+        let components = cells_to_components(&cells);
+        assert_eq!(components.len(), 2);
+        assert!(components.iter().all(|c| c.len() == 1));
+    }
+
+    #[test]
+    fn test_cells_to_components_connects_axial_neighbors() {
+        let cells = vec![
+            MazeCell { q: 0, r: 0, is_wall: false },
+            MazeCell { q: 1, r: 0, is_wall: false },
+        ];
+        let components = cells_to_components(&cells);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0]["0"], vec!["1".to_string()]);
+        assert_eq!(components[0]["1"], vec!["0".to_string()]);
+    }
+}
@@ -0,0 +1,57 @@
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::PyErr;
+
+/// Failures that can occur while solving a single maze component, once
+/// `maze_schema::validate` has already ruled out malformed input. Kept
+/// distinct from JSON/schema errors (which fail the whole call before any
+/// component-level work starts) so a caller can tell "this component's data
+/// was bad" from "the search itself couldn't produce a valid path" — the
+/// two used to be indistinguishable panics that took down the whole solve.
+#[derive(Debug)]
+pub enum SolverError {
+    /// The component has more cells than `MAX_NODE_COUNT` supports.
+    TooManyNodes { component: usize, count: usize, max: usize },
+    /// The search (and its one retry) both produced a path that fails
+    /// `validate_path` — a bug in the search, not bad input.
+    InvalidPathAfterRetry { component: usize },
+    /// The explicit-stack search hit `max_path_depth` before exhausting a
+    /// branch, so its result can't be trusted as the true longest path.
+    PathDepthExceeded { component: usize, limit: usize },
+    /// The search panicked on a background thread (e.g. a poisoned-mutex
+    /// `.unwrap()`) where pyo3 has no FFI boundary to catch it at, such as
+    /// the `rayon::spawn` worker behind `process_and_solve_maze_async`.
+    /// Caught with `std::panic::catch_unwind` so the caller gets an
+    /// exception instead of a future that never resolves.
+    Panicked(String),
+}
+
+impl std::fmt::Display for SolverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverError::TooManyNodes { component, count, max } => write!(
+                f,
+                "component {component} has {count} cells, exceeding the {max} supported"
+            ),
+            SolverError::InvalidPathAfterRetry { component } => write!(
+                f,
+                "component {component}: search produced an invalid path twice in a row"
+            ),
+            SolverError::PathDepthExceeded { component, limit } => write!(
+                f,
+                "component {component}: path search exceeded the configured depth limit of {limit}"
+            ),
+            SolverError::Panicked(message) => write!(f, "solve panicked: {message}"),
+        }
+    }
+}
+
+impl From<SolverError> for PyErr {
+    fn from(err: SolverError) -> PyErr {
+        match err {
+            SolverError::TooManyNodes { .. } => PyValueError::new_err(err.to_string()),
+            SolverError::InvalidPathAfterRetry { .. } => PyRuntimeError::new_err(err.to_string()),
+            SolverError::PathDepthExceeded { .. } => PyValueError::new_err(err.to_string()),
+            SolverError::Panicked(_) => PyRuntimeError::new_err(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::rust_maze_solver::MazeData;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+fn escape_dot_id(id: &str) -> String {
+    format!("\"{}\"", id.replace('"', "\\\""))
+}
+
+fn escape_xml(id: &str) -> String {
+    id.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn path_edges(path: Option<&[String]>) -> HashSet<(String, String)> {
+    path.map(|p| p.windows(2).map(|w| (w[0].clone(), w[1].clone())).collect())
+        .unwrap_or_default()
+}
+
+fn on_path(edges: &HashSet<(String, String)>, a: &str, b: &str) -> bool {
+    edges.contains(&(a.to_string(), b.to_string())) || edges.contains(&(b.to_string(), a.to_string()))
+}
+
+/// Renders one component as Graphviz DOT, with edges on `path` (if given)
+/// styled distinctly so the found path stands out when the graph is
+/// inspected visually.
+fn component_to_dot(component: &HashMap<String, Vec<String>>, path: Option<&[String]>) -> String {
+    let edges = path_edges(path);
+    let mut out = String::from("graph maze {\n");
+
+    for cell in component.keys() {
+        out.push_str(&format!("  {};\n", escape_dot_id(cell)));
+    }
+
+    let mut seen = HashSet::new();
+    for (cell, neighbors) in component {
+        for neighbor in neighbors {
+            let key = if cell < neighbor { (cell.clone(), neighbor.clone()) } else { (neighbor.clone(), cell.clone()) };
+            if !seen.insert(key) {
+                continue;
+            }
+            if on_path(&edges, cell, neighbor) {
+                out.push_str(&format!("  {} -- {} [color=red, penwidth=2];\n", escape_dot_id(cell), escape_dot_id(neighbor)));
+            } else {
+                out.push_str(&format!("  {} -- {};\n", escape_dot_id(cell), escape_dot_id(neighbor)));
+            }
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Renders one component as GraphML, with an `on_path` boolean edge
+/// attribute marking edges on `path` (if given).
+fn component_to_graphml(component: &HashMap<String, Vec<String>>, path: Option<&[String]>) -> String {
+    let edges = path_edges(path);
+    let mut out = String::from("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str("  <key id=\"on_path\" for=\"edge\" attr.name=\"on_path\" attr.type=\"boolean\"/>\n");
+    out.push_str("  <graph id=\"maze\" edgedefault=\"undirected\">\n");
+
+    for cell in component.keys() {
+        out.push_str(&format!("    <node id=\"{}\"/>\n", escape_xml(cell)));
+    }
+
+    let mut seen = HashSet::new();
+    let mut edge_id = 0usize;
+    for (cell, neighbors) in component {
+        for neighbor in neighbors {
+            let key = if cell < neighbor { (cell.clone(), neighbor.clone()) } else { (neighbor.clone(), cell.clone()) };
+            if !seen.insert(key) {
+                continue;
+            }
+            out.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\"><data key=\"on_path\">{}</data></edge>\n",
+                edge_id,
+                escape_xml(cell),
+                escape_xml(neighbor),
+                on_path(&edges, cell, neighbor)
+            ));
+            edge_id += 1;
+        }
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Exports the parsed maze graph as GraphML or Graphviz DOT, one document
+/// per component, so a weird solve result can be inspected in standard
+/// graph tools instead of by eye. `path`, if given, is a per-component list
+/// of node names (same shape as `MazeData::warm_start`) whose edges are
+/// highlighted in the export.
+#[pyfunction]
+#[pyo3(signature = (data, format="dot", path=None))]
+pub fn export_graph(data: String, format: &str, path: Option<Vec<Vec<String>>>) -> PyResult<Vec<String>> {
+    let maze_data: MazeData = serde_json::from_str(&data)
+        .map_err(|e| PyErr::new::<PyValueError, _>(format!("JSON error: {}", e)))?;
+
+    if let Err(errors) = crate::maze_schema::validate(&maze_data) {
+        return Err(PyErr::new::<PyValueError, _>(errors.join("; ")));
+    }
+
+    maze_data
+        .components
+        .iter()
+        .enumerate()
+        .map(|(index, component)| {
+            let component_path = path.as_ref().and_then(|p| p.get(index)).map(|v| v.as_slice());
+            match format {
+                "dot" => Ok(component_to_dot(component, component_path)),
+                "graphml" => Ok(component_to_graphml(component, component_path)),
+                other => Err(PyErr::new::<PyValueError, _>(format!(
+                    "unsupported export format '{other}' (expected 'dot' or 'graphml')"
+                ))),
+            }
+        })
+        .collect()
+}
@@ -3,9 +3,20 @@ use pyo3::wrap_pyfunction;
 use pyo3::types::PyModule;
 use pyo3::types::PyModuleMethods;
 
+pub mod bench_support;
+mod cells;
+mod errors;
+mod graph_export;
+mod maze_gen;
+mod maze_graph;
+mod maze_schema;
 mod rust_maze_solver;
 
-use rust_maze_solver::process_and_solve_maze;
+use cells::solve_from_cells;
+use graph_export::export_graph;
+use maze_gen::generate_maze;
+use maze_graph::MazeGraph;
+use rust_maze_solver::{process_and_solve_maze, process_and_solve_maze_async, process_and_solve_mazes, clear_solver_cache, solver_cache_stats, CancelHandle};
 
 /// A Python module implemented in Rust.
 #[pymodule]
@@ -18,6 +29,15 @@ fn rust_maze_solver_module(py: Python<'_>, module: &Bound<'_, PyModule>) -> PyRe
         .unwrap_or_else(|e| eprintln!("Failed to build thread pool: {}", e));
     
     module.add_function(wrap_pyfunction!(process_and_solve_maze, py)?)?;
+    module.add_function(wrap_pyfunction!(process_and_solve_maze_async, py)?)?;
+    module.add_function(wrap_pyfunction!(process_and_solve_mazes, py)?)?;
+    module.add_function(wrap_pyfunction!(generate_maze, py)?)?;
+    module.add_function(wrap_pyfunction!(clear_solver_cache, py)?)?;
+    module.add_function(wrap_pyfunction!(solver_cache_stats, py)?)?;
+    module.add_function(wrap_pyfunction!(export_graph, py)?)?;
+    module.add_function(wrap_pyfunction!(solve_from_cells, py)?)?;
+    module.add_class::<CancelHandle>()?;
+    module.add_class::<MazeGraph>()?;
 
    // Add docstring
     module.add("__doc__", "Optimized maze solving implementation in Rust with parallel processing and fixed memory allocation.")?;
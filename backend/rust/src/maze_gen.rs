@@ -0,0 +1,215 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// The six axial-coordinate step directions on a pointy-top hex grid.
+pub(crate) const AXIAL_DIRECTIONS: [(i32, i32); 6] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, -1), (-1, 1)];
+
+#[derive(Serialize)]
+struct GeneratedMazeData {
+    components: Vec<HashMap<String, Vec<String>>>,
+}
+
+/// A hex grid's axial-coordinate-to-id lookup, paired with the coordinate
+/// for each id (`coords[id]`), as returned by `hex_grid_ids`.
+type HexGrid = (HashMap<(i32, i32), u32>, Vec<(i32, i32)>);
+
+/// Lays out a hexagon-shaped grid of the given radius in axial coordinates
+/// and assigns each cell a linear id, row by row, so that cells in the same
+/// row get consecutive ids (matching the id scheme `sort_neighbors_clockwise`
+/// already assumes elsewhere in this crate).
+fn hex_grid_ids(radius: i32) -> HexGrid {
+    let mut coords = Vec::new();
+    for r in -radius..=radius {
+        let q_min = (-radius).max(-r - radius);
+        let q_max = radius.min(-r + radius);
+        for q in q_min..=q_max {
+            coords.push((q, r));
+        }
+    }
+
+    let id_of = coords
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| (c, i as u32))
+        .collect();
+
+    (id_of, coords)
+}
+
+/// Carves a perfect maze (spanning tree) over a hexagonal grid of `radius`
+/// using the recursive-backtracker algorithm, seeded for reproducibility.
+/// `pub(crate)` (rather than private) so `bench_support` can generate
+/// benchmark fixtures without going through the PyO3-facing `generate_maze`.
+pub(crate) fn carve_recursive_backtracker(radius: u32, seed: u64) -> HashMap<String, Vec<String>> {
+    let (id_of, coords) = hex_grid_ids(radius as i32);
+    let node_count = coords.len();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut visited = vec![false; node_count];
+    let mut adjacency: HashMap<String, Vec<String>> = (0..node_count)
+        .map(|id| (id.to_string(), Vec::new()))
+        .collect();
+
+    let mut stack = vec![0usize];
+    visited[0] = true;
+
+    while let Some(&current) = stack.last() {
+        let (cq, cr) = coords[current];
+        let unvisited_neighbors: Vec<usize> = AXIAL_DIRECTIONS
+            .iter()
+            .filter_map(|(dq, dr)| id_of.get(&(cq + dq, cr + dr)))
+            .map(|&id| id as usize)
+            .filter(|&id| !visited[id])
+            .collect();
+
+        if unvisited_neighbors.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let next = unvisited_neighbors[rng.random_range(0..unvisited_neighbors.len())];
+        visited[next] = true;
+        adjacency.get_mut(&current.to_string()).unwrap().push(next.to_string());
+        adjacency.get_mut(&next.to_string()).unwrap().push(current.to_string());
+        stack.push(next);
+    }
+
+    adjacency
+}
+
+/// Carves a perfect maze over a hexagonal grid of `radius` using Wilson's
+/// algorithm (loop-erased random walks), seeded for reproducibility. Unlike
+/// `carve_recursive_backtracker`'s depth-first bias (long winding
+/// corridors), every spanning tree over the grid is equally likely here,
+/// which tends to produce more uniformly branchy mazes.
+pub(crate) fn carve_wilsons(radius: u32, seed: u64) -> HashMap<String, Vec<String>> {
+    let (id_of, coords) = hex_grid_ids(radius as i32);
+    let node_count = coords.len();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut in_maze = vec![false; node_count];
+    let mut adjacency: HashMap<String, Vec<String>> = (0..node_count)
+        .map(|id| (id.to_string(), Vec::new()))
+        .collect();
+
+    if node_count == 0 {
+        return adjacency;
+    }
+    in_maze[0] = true;
+
+    let neighbors_of = |id: usize| -> Vec<usize> {
+        let (q, r) = coords[id];
+        AXIAL_DIRECTIONS
+            .iter()
+            .filter_map(|(dq, dr)| id_of.get(&(q + dq, r + dr)))
+            .map(|&n| n as usize)
+            .collect()
+    };
+
+    for start in 0..node_count {
+        if in_maze[start] {
+            continue;
+        }
+
+        // Random-walk from `start` until it hits the growing maze, erasing
+        // any loop the walk crosses back over as it goes so the final path
+        // carved into the maze is always simple.
+        let mut walk = vec![start];
+        let mut position_in_walk: HashMap<usize, usize> = HashMap::from([(start, 0)]);
+        let mut current = start;
+        while !in_maze[current] {
+            let neighbors = neighbors_of(current);
+            let next = neighbors[rng.random_range(0..neighbors.len())];
+            if let Some(&loop_start) = position_in_walk.get(&next) {
+                for node in walk.drain((loop_start + 1)..) {
+                    position_in_walk.remove(&node);
+                }
+            } else {
+                position_in_walk.insert(next, walk.len());
+                walk.push(next);
+            }
+            current = next;
+        }
+
+        for pair in walk.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            in_maze[a] = true;
+            adjacency.get_mut(&a.to_string()).unwrap().push(b.to_string());
+            adjacency.get_mut(&b.to_string()).unwrap().push(a.to_string());
+        }
+        in_maze[*walk.last().unwrap()] = true;
+    }
+
+    adjacency
+}
+
+/// Procedurally generates a hexagonal maze and returns it as a JSON string
+/// matching the `MazeData` schema consumed by `process_and_solve_maze`, so
+/// the solver can be exercised without an external maze file. `algorithm`
+/// picks the carving method: `"recursive-backtracker"` (the default) or
+/// `"wilson"`; an unrecognized name falls back to the default rather than
+/// erroring, matching `strategy_by_name`'s convention elsewhere in this
+/// crate.
+///
+/// There's no CLI entry point here (e.g. a `--generate <radius>` flag) —
+/// this crate is a pure PyO3 extension module with no `bin/` target, so a
+/// standalone CLI isn't applicable; `generate_maze` is reached from Python
+/// instead.
+#[pyfunction]
+#[pyo3(signature = (radius, seed=None, algorithm=None))]
+pub fn generate_maze(radius: u32, seed: Option<u64>, algorithm: Option<String>) -> PyResult<String> {
+    if radius == 0 {
+        return Err(PyErr::new::<PyValueError, _>("radius must be >= 1"));
+    }
+
+    let adjacency = match algorithm.as_deref() {
+        Some("wilson") => carve_wilsons(radius, seed.unwrap_or(0)),
+        _ => carve_recursive_backtracker(radius, seed.unwrap_or(0)),
+    };
+    let data = GeneratedMazeData { components: vec![adjacency] };
+
+    serde_json::to_string(&data)
+        .map_err(|e| PyErr::new::<PyValueError, _>(format!("serialize error: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_maze_is_deterministic_for_seed() {
+        let a = generate_maze(2, Some(42), None).unwrap();
+        let b = generate_maze(2, Some(42), None).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_generate_maze_rejects_zero_radius() {
+        assert!(generate_maze(0, None, None).is_err());
+    }
+
+    #[test]
+    fn test_carve_spans_every_cell() {
+        let (id_of, _) = hex_grid_ids(3);
+        let adjacency = carve_recursive_backtracker(3, 7);
+        assert_eq!(adjacency.len(), id_of.len());
+        assert!(adjacency.values().all(|neighbors| !neighbors.is_empty()) || id_of.len() == 1);
+    }
+
+    #[test]
+    fn test_carve_wilsons_spans_every_cell() {
+        let (id_of, _) = hex_grid_ids(3);
+        let adjacency = carve_wilsons(3, 7);
+        assert_eq!(adjacency.len(), id_of.len());
+        assert!(adjacency.values().all(|neighbors| !neighbors.is_empty()) || id_of.len() == 1);
+    }
+
+    #[test]
+    fn test_generate_maze_wilson_algorithm_is_deterministic_for_seed() {
+        let a = generate_maze(2, Some(42), Some("wilson".to_string())).unwrap();
+        let b = generate_maze(2, Some(42), Some("wilson".to_string())).unwrap();
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::rust_maze_solver::{
+    build_graph_from_adjacency, sort_neighbors_clockwise, strategy_by_name, Graph, NodeId, MAX_NODE_COUNT,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Wraps one component's internal `Graph` plus its id/name mapping so
+/// advanced callers can script custom analyses (centrality, alternate
+/// traversals, ad-hoc pathfinding) directly against the same graph the
+/// solver builds, without round-tripping adjacency JSON for every query.
+#[pyclass]
+pub struct MazeGraph {
+    graph: Graph,
+    id_to_name: Vec<String>,
+    name_to_id: HashMap<String, NodeId>,
+}
+
+#[pymethods]
+impl MazeGraph {
+    /// Builds a `MazeGraph` from one component's adjacency list, in the
+    /// same `{name: [neighbor_names]}` shape as a `MazeData` component.
+    #[new]
+    fn new(adjacency_json: String) -> PyResult<Self> {
+        let adjacency: HashMap<String, Vec<String>> = serde_json::from_str(&adjacency_json)
+            .map_err(|e| PyErr::new::<PyValueError, _>(format!("JSON error: {}", e)))?;
+        let sorted = sort_neighbors_clockwise(&adjacency);
+        let (graph, id_to_name) = build_graph_from_adjacency(&sorted);
+        let name_to_id = id_to_name.iter().enumerate().map(|(id, name)| (name.clone(), id as NodeId)).collect();
+        Ok(MazeGraph { graph, id_to_name, name_to_id })
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn neighbors(&self, node: &str) -> PyResult<Vec<String>> {
+        let id = self.id_for(node)?;
+        Ok(self.graph.get_neighbors(id).iter().map(|&n| self.name(n)).collect())
+    }
+
+    fn degree(&self, node: &str) -> PyResult<usize> {
+        let id = self.id_for(node)?;
+        Ok(self.graph.get_neighbors(id).len())
+    }
+
+    /// Breadth-first traversal order starting at `node`, as node names.
+    fn bfs(&self, node: &str) -> PyResult<Vec<String>> {
+        let start = self.id_for(node)?;
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start);
+        queue.push_back(start);
+        while let Some(id) = queue.pop_front() {
+            order.push(self.name(id));
+            for &neighbor in self.graph.get_neighbors(id) {
+                if visited.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Exact longest simple path over the whole graph, as node names. Runs
+    /// the same default `ExactBacktracking` search `process_and_solve_maze`
+    /// uses, minus tracing/cancellation/warm-start plumbing.
+    fn longest_path(&self) -> Vec<String> {
+        let strategy = strategy_by_name(None);
+        let max_depth = self.graph.node_count().min(MAX_NODE_COUNT);
+        let (path, _) = strategy.search(&self.graph, None, &[], None, None, 0, max_depth, false);
+        path.into_iter().map(|id| self.name(id)).collect()
+    }
+}
+
+impl MazeGraph {
+    fn id_for(&self, node: &str) -> PyResult<NodeId> {
+        self.name_to_id
+            .get(node)
+            .copied()
+            .ok_or_else(|| PyErr::new::<PyValueError, _>(format!("unknown node '{node}'")))
+    }
+
+    fn name(&self, id: NodeId) -> String {
+        self.id_to_name[id as usize].clone()
+    }
+}
@@ -0,0 +1,137 @@
+use crate::rust_maze_solver::MazeData;
+
+/// The current schema version this crate's `MazeData` model understands.
+/// Payloads omitting `schemaVersion` are treated as version 1, for callers
+/// written before this field existed.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Validates a deserialized `MazeData` payload before it reaches the
+/// solver, so malformed input (an undefined neighbor reference, a stale
+/// `must_visit`/`warm_start` cell name, an unsupported schema version)
+/// produces a clear per-field error message instead of a cryptic panic or
+/// a silently dropped reference deep inside graph construction.
+pub fn validate(data: &MazeData) -> Result<(), Vec<String>> {
+    let mut errors = schema_version_errors(data);
+    for component_errors in validate_per_component(data) {
+        errors.extend(component_errors);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Payload-level errors that aren't tied to any single component (so a
+/// lenient caller can't work around them by skipping components).
+pub fn schema_version_errors(data: &MazeData) -> Vec<String> {
+    let mut errors = Vec::new();
+    if let Some(version) = data.schema_version {
+        if version != CURRENT_SCHEMA_VERSION {
+            errors.push(format!(
+                "schemaVersion {version} is not supported (expected {CURRENT_SCHEMA_VERSION})"
+            ));
+        }
+    }
+    errors
+}
+
+/// Validates each component independently, returning one error list per
+/// component (indexed the same as `data.components`, empty where that
+/// component is well-formed). Split out from `validate` so a lenient caller
+/// can skip only the components that actually fail, instead of the whole
+/// payload.
+pub fn validate_per_component(data: &MazeData) -> Vec<Vec<String>> {
+    data.components
+        .iter()
+        .enumerate()
+        .map(|(index, component)| {
+            let mut errors = Vec::new();
+
+            for (cell, neighbors) in component {
+                for neighbor in neighbors {
+                    if !component.contains_key(neighbor) {
+                        errors.push(format!(
+                            "component {index}: cell '{cell}' references undefined neighbor '{neighbor}'"
+                        ));
+                    }
+                }
+            }
+
+            if let Some(names) = data.must_visit.get(index) {
+                validate_names_exist(component, index, "must_visit", names, &mut errors);
+            }
+            if let Some(names) = data.warm_start.get(index) {
+                validate_names_exist(component, index, "warm_start", names, &mut errors);
+            }
+            if let Some(names) = data.endpoints.get(index) {
+                if !names.is_empty() && names.len() != 2 {
+                    errors.push(format!(
+                        "component {index}: endpoints must have exactly 2 entries (start, end), got {}",
+                        names.len()
+                    ));
+                } else {
+                    validate_names_exist(component, index, "endpoints", names, &mut errors);
+                }
+            }
+            if let Some(coords) = data.coords.get(index) {
+                validate_axial_consistency(component, index, coords, &mut errors);
+            }
+
+            errors
+        })
+        .collect()
+}
+
+/// Checks that a component's optional `coords` agree with its declared
+/// adjacency: every cell named in `coords` must exist in the component (no
+/// orphaned entries), and every adjacency edge
+/// between two coordinate-bearing cells must step along one of the six
+/// axial directions (`q + r + s == 0` holds automatically for any `(q, r)`
+/// pair since `s` is never stored explicitly — the coordinates that
+/// actually need checking are whether they agree with the graph's own
+/// notion of "neighbor").
+fn validate_axial_consistency(
+    component: &std::collections::HashMap<String, Vec<String>>,
+    index: usize,
+    coords: &std::collections::HashMap<String, (i32, i32)>,
+    errors: &mut Vec<String>,
+) {
+    for name in coords.keys() {
+        if !component.contains_key(name) {
+            errors.push(format!(
+                "component {index}: coords references undefined cell '{name}'"
+            ));
+        }
+    }
+
+    for (cell, neighbors) in component {
+        let Some(&cell_coord) = coords.get(cell) else { continue };
+        for neighbor in neighbors {
+            let Some(&neighbor_coord) = coords.get(neighbor) else { continue };
+            let delta = (neighbor_coord.0 - cell_coord.0, neighbor_coord.1 - cell_coord.1);
+            if !crate::maze_gen::AXIAL_DIRECTIONS.contains(&delta) {
+                errors.push(format!(
+                    "component {index}: cell '{cell}' at {cell_coord:?} is not axially adjacent to neighbor '{neighbor}' at {neighbor_coord:?}"
+                ));
+            }
+        }
+    }
+}
+
+fn validate_names_exist(
+    component: &std::collections::HashMap<String, Vec<String>>,
+    index: usize,
+    field: &str,
+    names: &[String],
+    errors: &mut Vec<String>,
+) {
+    for name in names {
+        if !component.contains_key(name) {
+            errors.push(format!(
+                "component {index}: {field} references undefined cell '{name}'"
+            ));
+        }
+    }
+}
@@ -1,22 +1,28 @@
 use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 use rayon::prelude::*;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use heapless::{IndexMap, Vec as HeaplessVec};
 use nohash_hasher;
 use nohash_hasher::NoHashHasher as NoHashHasherType;
 use hash32::BuildHasherDefault as HashConstructor;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use std::thread;
+use std::sync::OnceLock;
+use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
 use serde::Deserialize;
 use serde_json;
+use crate::errors::SolverError;
 
 // Type aliases with heapless for fixed memory usage
-type NodeId = u32;
+pub(crate) type NodeId = u32;
 
 /// We assume a maximum of 2048 nodes. Each bit in the bitset corresponds to a node ID.
-const MAX_NODE_COUNT: usize = 2048;
+pub(crate) const MAX_NODE_COUNT: usize = 2048;
 const BITSET_ARRAY_SIZE: usize = MAX_NODE_COUNT / 64;
 
 /// A memory-efficient bitset for tracking node visitation
@@ -71,9 +77,32 @@ impl std::fmt::Debug for NodeBitset {
     }
 }
 
+/// Bitmask (bit `i` set means `neighbors[i]` is already in `visited`)
+/// computed over a whole neighbor row in one pass instead of one
+/// `visited.contains` call per candidate as the DFS scans forward. A hex
+/// node has at most 6 edges and `Graph`'s adjacency caps each row at 8
+/// (see `HeaplessVec<NodeId, 8>` below), so the row and its mask both fit
+/// comfortably in a `u8`.
+///
+/// True `std::simd`/portable-SIMD lane comparisons would do this same
+/// up-to-8-wide check in a single instruction, but `portable_simd` is
+/// still nightly-only and this crate builds on stable pyo3/maturin
+/// toolchains — this mask gets the same "test every neighbor against the
+/// bitset together" benefit the profiler asked for without an unstable
+/// feature gate. Swap in `std::simd::u32x8`/`Mask` here once portable-SIMD
+/// stabilizes, comparing this against that via `benches/solver_benchmarks.rs`.
+#[inline]
+fn neighbor_visited_mask(neighbors: &[NodeId], visited: &NodeBitset) -> u8 {
+    let mut mask = 0u8;
+    for (i, &neighbor) in neighbors.iter().enumerate() {
+        mask |= (visited.contains(neighbor) as u8) << i;
+    }
+    mask
+}
+
 // Graph structure
 #[derive(Debug, Clone)]
-struct Graph {
+pub(crate) struct Graph {
     adjacency: IndexMap<NodeId, HeaplessVec<NodeId, 8>, HashConstructor<NoHashHasherType<NodeId>>, 2048>,
 }
 
@@ -84,11 +113,11 @@ impl Graph {
         }
     }
 
-    fn node_count(&self) -> usize {
+    pub(crate) fn node_count(&self) -> usize {
         self.adjacency.len()
     }
     
-    fn nodes(&self) -> Vec<NodeId> {
+    pub(crate) fn nodes(&self) -> Vec<NodeId> {
         self.adjacency.keys().copied().collect()
     }
     
@@ -124,13 +153,13 @@ impl Graph {
         Ok(())
     }
 
-    fn get_neighbors(&self, node: NodeId) -> &[NodeId] {
+    pub(crate) fn get_neighbors(&self, node: NodeId) -> &[NodeId] {
         self.adjacency.get(&node).map_or(&[], |v| v.as_slice())
     }
 }
 
 // Graph building
-fn build_graph_from_adjacency(adjacency_list: &HashMap<String, Vec<String>>) -> (Graph, Vec<String>) {
+pub(crate) fn build_graph_from_adjacency(adjacency_list: &HashMap<String, Vec<String>>) -> (Graph, Vec<String>) {
     let mut name_to_id = HashMap::new();
     let mut id_to_name = Vec::new();
     
@@ -158,7 +187,7 @@ fn build_graph_from_adjacency(adjacency_list: &HashMap<String, Vec<String>>) ->
 }
 
 // Helper function to sort neighbors in clockwise order
-fn sort_neighbors_clockwise(adjacency_list: &HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+pub(crate) fn sort_neighbors_clockwise(adjacency_list: &HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
     let mut sorted_adjacency_list = HashMap::new();
     
     for (center_str, neighbors) in adjacency_list {
@@ -212,14 +241,208 @@ fn sort_neighbors_clockwise(adjacency_list: &HashMap<String, Vec<String>>) -> Ha
         
         sorted_adjacency_list.insert(center_str.clone(), result);
     }
-    
+
     sorted_adjacency_list
 }
 
+/// Neighbor visitation order applied before a component's search starts.
+/// `sort_neighbors_clockwise`'s row-major-numeric-id assumption breaks down
+/// for id schemes it wasn't designed for, so callers that know their ids
+/// don't fit that shape (or want to A/B different heuristics) can pick one
+/// of these instead via `search_strategy`'s sibling knob, `neighbor_ordering`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NeighborOrdering {
+    /// The original heuristic: infer clockwise order from numeric,
+    /// row-major node names.
+    ClockwiseNumeric,
+    /// Sort neighbors by angle around the center using real axial
+    /// coordinates, so ordering no longer depends on how ids were named.
+    ClockwiseCoords,
+    /// Visit lower-degree neighbors first ("most-constrained-first"), a
+    /// common backtracking heuristic that tends to hit dead ends sooner.
+    DegreeAscending,
+    /// Shuffle each neighbor list with a seeded RNG, for A/B-testing
+    /// whether a given component is sensitive to ordering at all.
+    Random(u64),
+}
+
+/// Resolves a neighbor-ordering name to an implementation, defaulting
+/// unknown names to `ClockwiseNumeric` (today's existing behavior) so an
+/// unrecognized value degrades safely instead of silently changing output.
+pub(crate) fn neighbor_ordering_by_name(name: Option<&str>, seed: Option<u64>) -> NeighborOrdering {
+    match name {
+        Some("clockwise-coords") => NeighborOrdering::ClockwiseCoords,
+        Some("degree-ascending") => NeighborOrdering::DegreeAscending,
+        Some("random") => NeighborOrdering::Random(seed.unwrap_or(0)),
+        _ => NeighborOrdering::ClockwiseNumeric,
+    }
+}
+
+/// Reorders each node's neighbor list per `ordering` before the search
+/// walks it. `coords` maps node name to axial `(q, r)` coordinates and is
+/// only consulted by `ClockwiseCoords`; if it's absent there, ordering
+/// falls back to `ClockwiseNumeric` for the whole component rather than
+/// erroring, since a missing coordinate map just means the caller didn't
+/// have one to give.
+pub(crate) fn sort_neighbors(
+    adjacency_list: &HashMap<String, Vec<String>>,
+    ordering: NeighborOrdering,
+    coords: Option<&HashMap<String, (i32, i32)>>,
+) -> HashMap<String, Vec<String>> {
+    match ordering {
+        NeighborOrdering::ClockwiseNumeric => sort_neighbors_clockwise(adjacency_list),
+        NeighborOrdering::ClockwiseCoords => sort_neighbors_clockwise_coords(adjacency_list, coords),
+        NeighborOrdering::DegreeAscending => sort_neighbors_degree_ascending(adjacency_list),
+        NeighborOrdering::Random(seed) => sort_neighbors_random(adjacency_list, seed),
+    }
+}
+
+fn sort_neighbors_clockwise_coords(
+    adjacency_list: &HashMap<String, Vec<String>>,
+    coords: Option<&HashMap<String, (i32, i32)>>,
+) -> HashMap<String, Vec<String>> {
+    let Some(coords) = coords else {
+        return sort_neighbors_clockwise(adjacency_list);
+    };
+
+    let angle_from = |center: &str, node: &str| -> f64 {
+        match (coords.get(center), coords.get(node)) {
+            (Some(&(cx, cy)), Some(&(nx, ny))) => f64::from(ny - cy).atan2(f64::from(nx - cx)),
+            _ => 0.0,
+        }
+    };
+
+    adjacency_list
+        .iter()
+        .map(|(center_str, neighbors)| {
+            let mut sorted = neighbors.clone();
+            sorted.sort_by(|a, b| {
+                angle_from(center_str, a)
+                    .partial_cmp(&angle_from(center_str, b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            (center_str.clone(), sorted)
+        })
+        .collect()
+}
+
+fn sort_neighbors_degree_ascending(adjacency_list: &HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+    adjacency_list
+        .iter()
+        .map(|(center_str, neighbors)| {
+            let mut sorted = neighbors.clone();
+            sorted.sort_by_key(|name| adjacency_list.get(name).map_or(0, |n| n.len()));
+            (center_str.clone(), sorted)
+        })
+        .collect()
+}
+
+/// Shuffles each neighbor list with a `StdRng` seeded from `seed`, so the
+/// same seed always reproduces the same ordering (needed for
+/// `benches/solver_benchmarks.rs` to compare orderings across runs).
+fn sort_neighbors_random(adjacency_list: &HashMap<String, Vec<String>>, seed: u64) -> HashMap<String, Vec<String>> {
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    adjacency_list
+        .iter()
+        .map(|(center_str, neighbors)| {
+            let mut sorted = neighbors.clone();
+            sorted.shuffle(&mut rng);
+            (center_str.clone(), sorted)
+        })
+        .collect()
+}
+
+/// Handle a Python caller can use to abort an in-progress solve.
+///
+/// Passed into `process_and_solve_maze` and checked periodically from
+/// inside the backtracking loops (which run under `allow_threads`, so a
+/// timed-out web request can call `cancel()` from another thread instead of
+/// waiting for the search to finish on its own).
+#[pyclass]
+#[derive(Clone)]
+pub struct CancelHandle {
+    flag: Arc<AtomicBool>,
+}
+
+#[pymethods]
+impl CancelHandle {
+    #[new]
+    fn new() -> Self {
+        CancelHandle { flag: Arc::new(AtomicBool::new(false)) }
+    }
+
+    fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+/// Maps a (from, to) directed step in a contracted graph to the number of
+/// original nodes it represents (1 for an uncontracted edge, or
+/// `1 + interior_chain_len` for a contracted degree-2 chain). Looked up by
+/// `edge_weight` during the search so that "longest path" is judged by
+/// original node count, not contracted node count.
+type EdgeWeights = HashMap<(NodeId, NodeId), usize>;
+
+fn edge_weight(edge_weights: Option<&EdgeWeights>, from: NodeId, to: NodeId) -> usize {
+    edge_weights.and_then(|w| w.get(&(from, to))).copied().unwrap_or(1)
+}
+
+/// A shared, lock-protected pool of `(weight, path)` candidates, one entry
+/// per start node that beat the running best, collected in `deterministic`
+/// mode so they can be reduced to a single winner in a fixed order.
+type WeightedCandidates = Arc<Mutex<Vec<(usize, Vec<NodeId>)>>>;
+
+/// Periodically records the in-progress path during the search so callers
+/// can animate "the solver thinking" on the frontend instead of only ever
+/// seeing the final result. Sampling is throttled by `interval` (in
+/// backtracking calls, not wall-clock time) since dense components make
+/// thousands of recursive calls per millisecond.
+pub(crate) struct SearchTrace {
+    interval: usize,
+    counter: AtomicUsize,
+    samples: Mutex<Vec<Vec<NodeId>>>,
+}
+
+impl SearchTrace {
+    fn new(interval: usize) -> Self {
+        SearchTrace { interval, counter: AtomicUsize::new(0), samples: Mutex::new(Vec::new()) }
+    }
+
+    fn record(&self, path: &[NodeId]) {
+        let count = self.counter.fetch_add(1, Ordering::Relaxed);
+        if count.is_multiple_of(self.interval) {
+            self.samples.lock().unwrap().push(path.to_vec());
+        }
+    }
+
+    fn into_samples(self) -> Vec<Vec<NodeId>> {
+        self.samples.into_inner().unwrap()
+    }
+}
+
 // Optimized brute force approach
-fn exact_longest_path_optimized(graph: &Graph) -> Vec<NodeId> {
+#[allow(clippy::too_many_arguments)]
+fn exact_longest_path_optimized(
+    graph: &Graph,
+    cancel: Option<&Arc<AtomicBool>>,
+    required: &[NodeId],
+    edge_weights: Option<&EdgeWeights>,
+    trace: Option<&SearchTrace>,
+    initial_best_weight: usize,
+    max_depth: usize,
+    deterministic: bool,
+) -> (Vec<NodeId>, bool) {
     let node_count = graph.node_count();
-    
+    let depth_exceeded = AtomicBool::new(false);
+
     // Pre-calculate and cache low-degree nodes to avoid repeated filtering
     let start_nodes: Vec<_> = {
         let mut nodes = Vec::with_capacity(node_count / 4);
@@ -230,42 +453,72 @@ fn exact_longest_path_optimized(graph: &Graph) -> Vec<NodeId> {
         }
         nodes
     };
-    
+
     // Use all nodes if not enough low-degree nodes found
     let nodes_to_try = if start_nodes.len() < 2 { graph.nodes() } else { start_nodes.clone() };
-    
-    let best_len = AtomicUsize::new(0);
+
+    // Seeding both the shared and per-thread bests from a caller-supplied
+    // warm-start weight (see `repair_warm_start`) lets the bound-pruning
+    // check in `backtrack_exact_standard_optimized` start cutting branches
+    // immediately, instead of only after the search stumbles on its own
+    // first candidate.
+    let best_weight = AtomicUsize::new(initial_best_weight);
     let result = Arc::new(Mutex::new(Vec::with_capacity(node_count)));
-    
+
+    // In non-deterministic mode, the first thread to CAS `best_weight` up to
+    // a given value wins ties, and which thread that is depends on
+    // scheduling. `deterministic` instead collects every start node's best
+    // candidate and reduces them in a fixed order below, so equal-length
+    // results always resolve to the same path regardless of thread count.
+    let candidates: WeightedCandidates = Arc::new(Mutex::new(Vec::new()));
+
     let thread_pool = rayon::ThreadPoolBuilder::new()
         .num_threads(std::thread::available_parallelism().map(|x| x.get()).unwrap_or(8))
         .build()
         .unwrap();
-    
+
     thread_pool.install(|| {
         nodes_to_try.par_iter().for_each(|&start_node| {
-            let mut local_best_len = 0;
+            if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+                return;
+            }
+
+            let mut local_best_weight = initial_best_weight;
             let mut local_best_path = Vec::with_capacity(node_count);
             let mut visited = NodeBitset::new();
             let mut path = Vec::with_capacity(node_count);
-            
+
             visited.set(start_node);
             path.push(start_node);
-            
+
             backtrack_exact_standard_optimized(
                 graph,
                 &mut path,
+                1, // path_weight: the start node alone represents one original node
                 &mut visited,
-                &mut local_best_len,
-                &mut local_best_path
+                &mut local_best_weight,
+                &mut local_best_path,
+                cancel,
+                required,
+                edge_weights,
+                trace,
+                max_depth,
+                &depth_exceeded,
             );
-            
-            let current_best = best_len.load(Ordering::Relaxed);
-            if local_best_len > current_best {
-                if best_len.compare_exchange(
-                    current_best, 
-                    local_best_len, 
-                    Ordering::SeqCst, 
+
+            if deterministic {
+                if !local_best_path.is_empty() {
+                    candidates.lock().unwrap().push((local_best_weight, local_best_path));
+                }
+                return;
+            }
+
+            let current_best = best_weight.load(Ordering::Relaxed);
+            if local_best_weight > current_best {
+                if best_weight.compare_exchange(
+                    current_best,
+                    local_best_weight,
+                    Ordering::SeqCst,
                     Ordering::Relaxed
                 ).is_ok() {
                     let mut path_guard = result.lock().unwrap();
@@ -274,204 +527,2012 @@ fn exact_longest_path_optimized(graph: &Graph) -> Vec<NodeId> {
             }
         });
     });
-    
-    let final_result = result.lock().unwrap().clone();
-    println!("Found path of {}/{} nodes ({}%)", 
-              final_result.len(), node_count, 
-              (final_result.len() as f32 * 100.0 / node_count as f32) as u32);
-    
-    final_result
+
+    let final_result = if deterministic {
+        let mut candidates = candidates.lock().unwrap();
+        // Highest weight wins; ties break on lexicographic node-id order so
+        // the outcome doesn't depend on which thread finished first.
+        candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        if let Some((weight, _)) = candidates.first() {
+            best_weight.store(*weight, Ordering::Relaxed);
+        }
+        if candidates.is_empty() { Vec::new() } else { candidates.remove(0).1 }
+    } else {
+        result.lock().unwrap().clone()
+    };
+    println!("Found path of {} contracted node(s), best weight {} ({}%)",
+              final_result.len(), best_weight.load(Ordering::Relaxed),
+              (best_weight.load(Ordering::Relaxed) as f32 * 100.0 / node_count.max(1) as f32) as u32);
+
+    (final_result, depth_exceeded.load(Ordering::Relaxed))
 }
 
-#[inline(always)]
-fn backtrack_exact_standard_optimized(
+/// Explicit-stack DFS for the longest simple cycle through `start`, mirroring
+/// `backtrack_exact_standard_optimized`'s frame-stack shape but closing back
+/// on `start` instead of tracking the deepest leaf: a candidate only updates
+/// `best_path` once the current node has an edge back to `start` and the
+/// cycle has at least 3 nodes, and `start` itself is never offered as an
+/// interior step so it's only ever revisited to close the loop.
+#[allow(clippy::too_many_arguments)]
+fn backtrack_longest_cycle(
     graph: &Graph,
     path: &mut Vec<NodeId>,
+    path_weight: usize,
     visited: &mut NodeBitset,
-    best_length: &mut usize,
+    start: NodeId,
+    best_weight: &mut usize,
     best_path: &mut Vec<NodeId>,
+    cancel: Option<&Arc<AtomicBool>>,
+    max_depth: usize,
+    depth_exceeded: &AtomicBool,
 ) {
-    if path.len() > *best_length {
-        *best_length = path.len();
-        best_path.clear();
-        best_path.extend_from_slice(path);
-    }
-    
-    let current = *path.last().unwrap();
-    let neighbors = graph.get_neighbors(current);
-    
-    match neighbors.len() {
-        0 => return, // Dead-end
-        1 => {
-            let neighbor = neighbors[0];
-            if !visited.contains(neighbor) {
+    let mut stack: Vec<SearchFrame> = Vec::with_capacity(max_depth.min(MAX_NODE_COUNT));
+    stack.push(SearchFrame { node: *path.last().unwrap(), next_neighbor: 0, weight: path_weight, entered: false });
+
+    while let Some(top) = stack.len().checked_sub(1) {
+        if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+            return;
+        }
+
+        let node = stack[top].node;
+        let weight = stack[top].weight;
+
+        if !stack[top].entered {
+            stack[top].entered = true;
+
+            if path.len() >= 3 && weight > *best_weight && graph.get_neighbors(node).contains(&start) {
+                *best_weight = weight;
+                best_path.clear();
+                best_path.extend_from_slice(path);
+            }
+        }
+
+        if path.len() >= max_depth {
+            // Only a branch that could still grow is cut short by the limit.
+            let neighbors = graph.get_neighbors(node);
+            if (neighbor_visited_mask(neighbors, visited).count_ones() as usize) < neighbors.len() {
+                depth_exceeded.store(true, Ordering::Relaxed);
+            }
+            pop_frame(&mut stack, path, visited, node);
+            continue;
+        }
+
+        let neighbors = graph.get_neighbors(node);
+        let visited_mask = neighbor_visited_mask(neighbors, visited);
+        let mut next_child = None;
+        while stack[top].next_neighbor < neighbors.len() {
+            let i = stack[top].next_neighbor;
+            let neighbor = neighbors[i];
+            stack[top].next_neighbor += 1;
+            if neighbor != start && visited_mask & (1 << i) == 0 {
+                next_child = Some(neighbor);
+                break;
+            }
+        }
+
+        match next_child {
+            Some(neighbor) => {
                 visited.set(neighbor);
                 path.push(neighbor);
-                
-                backtrack_exact_standard_optimized(
-                    graph, path, visited, best_length, best_path
-                );
-                
-                path.pop();
-                visited.clear(neighbor);
-            }
-        },
-        2 => {
-            let n1 = neighbors[0];
-            let n2 = neighbors[1];
-            
-            if !visited.contains(n1) {
-                visited.set(n1);
-                path.push(n1);
-                
-                backtrack_exact_standard_optimized(
-                    graph, path, visited, best_length, best_path
-                );
-                
-                path.pop();
-                visited.clear(n1);
+                stack.push(SearchFrame {
+                    node: neighbor,
+                    next_neighbor: 0,
+                    weight: weight + edge_weight(None, node, neighbor),
+                    entered: false,
+                });
             }
-            
-            if !visited.contains(n2) {
-                visited.set(n2);
-                path.push(n2);
-                
-                backtrack_exact_standard_optimized(
-                    graph, path, visited, best_length, best_path
-                );
-                
-                path.pop();
-                visited.clear(n2);
-            }
-        },
-        _ => {
-            for &neighbor in neighbors {
-                if !visited.contains(neighbor) {
-                    visited.set(neighbor);
-                    path.push(neighbor);
-                    
-                    backtrack_exact_standard_optimized(
-                        graph, path, visited, best_length, best_path
-                    );
-                    
-                    path.pop();
-                    visited.clear(neighbor);
+            None => pop_frame(&mut stack, path, visited, node),
+        }
+    }
+}
+
+/// Longest simple cycle in `graph`, parallelized across every node as a
+/// candidate start (unlike `exact_longest_path_optimized`, a cycle offers no
+/// low-degree shortcut for seeding: any node on the cycle is a valid start).
+/// Returns the cycle as a plain node sequence (start not repeated at the
+/// end; callers append it when they need an explicit closed loop) and
+/// whether `max_depth` was hit before the search exhausted itself.
+fn exact_longest_cycle_optimized(
+    graph: &Graph,
+    cancel: Option<&Arc<AtomicBool>>,
+    max_depth: usize,
+    deterministic: bool,
+) -> (Vec<NodeId>, bool) {
+    let depth_exceeded = AtomicBool::new(false);
+    let best_weight = AtomicUsize::new(0);
+    let result = Arc::new(Mutex::new(Vec::new()));
+    let candidates: WeightedCandidates = Arc::new(Mutex::new(Vec::new()));
+
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(std::thread::available_parallelism().map(|x| x.get()).unwrap_or(8))
+        .build()
+        .unwrap();
+
+    let nodes = graph.nodes();
+
+    thread_pool.install(|| {
+        nodes.par_iter().for_each(|&start_node| {
+            if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+                return;
+            }
+
+            let mut local_best_weight = 0usize;
+            let mut local_best_path: Vec<NodeId> = Vec::new();
+            let mut visited = NodeBitset::new();
+            let mut path = vec![start_node];
+            visited.set(start_node);
+
+            backtrack_longest_cycle(
+                graph,
+                &mut path,
+                1,
+                &mut visited,
+                start_node,
+                &mut local_best_weight,
+                &mut local_best_path,
+                cancel,
+                max_depth,
+                &depth_exceeded,
+            );
+
+            if deterministic {
+                if !local_best_path.is_empty() {
+                    candidates.lock().unwrap().push((local_best_weight, local_best_path));
                 }
+                return;
             }
-        }
+
+            let current_best = best_weight.load(Ordering::Relaxed);
+            if local_best_weight > current_best
+                && best_weight.compare_exchange(current_best, local_best_weight, Ordering::SeqCst, Ordering::Relaxed).is_ok()
+            {
+                *result.lock().unwrap() = local_best_path;
+            }
+        });
+    });
+
+    let final_result = if deterministic {
+        let mut candidates = candidates.lock().unwrap();
+        candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        if candidates.is_empty() { Vec::new() } else { candidates.remove(0).1 }
+    } else {
+        result.lock().unwrap().clone()
+    };
+    (final_result, depth_exceeded.load(Ordering::Relaxed))
+}
+
+/// A pluggable longest-path search over a single component's graph, so new
+/// approaches can be dropped in and benchmarked against
+/// `ExactBacktracking` without forking the solver. Implementations share
+/// `exact_longest_path_optimized`'s signature: the returned `bool` reports
+/// whether the search hit `max_depth` before exhausting its search space
+/// (`false` for strategies, like `BeamSearch`, that don't bound depth that
+/// way).
+pub(crate) trait LongestPathStrategy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// `deterministic` breaks ties among equal-length candidates the same
+    /// way regardless of thread count, instead of the fastest thread
+    /// winning a race; strategies indifferent to thread-scheduling races
+    /// (e.g. `BeamSearch`, which never runs candidates concurrently) simply
+    /// ignore it.
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &self,
+        graph: &Graph,
+        cancel: Option<&Arc<AtomicBool>>,
+        required: &[NodeId],
+        edge_weights: Option<&EdgeWeights>,
+        trace: Option<&SearchTrace>,
+        initial_best_weight: usize,
+        max_depth: usize,
+        deterministic: bool,
+    ) -> (Vec<NodeId>, bool);
+}
+
+/// The original exhaustive backtracking search, parallelized across
+/// candidate start nodes. Always finds the true longest path (subject to
+/// `max_depth`); the default and only strategy this crate guaranteed
+/// correct results with before this trait existed.
+struct ExactBacktracking;
+
+impl LongestPathStrategy for ExactBacktracking {
+    fn name(&self) -> &'static str {
+        "exact-backtracking"
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &self,
+        graph: &Graph,
+        cancel: Option<&Arc<AtomicBool>>,
+        required: &[NodeId],
+        edge_weights: Option<&EdgeWeights>,
+        trace: Option<&SearchTrace>,
+        initial_best_weight: usize,
+        max_depth: usize,
+        deterministic: bool,
+    ) -> (Vec<NodeId>, bool) {
+        exact_longest_path_optimized(graph, cancel, required, edge_weights, trace, initial_best_weight, max_depth, deterministic)
     }
 }
 
-// Data structures for deserialization
-#[derive(Deserialize)]
-struct MazeData {
-    components: Vec<HashMap<String, Vec<String>>>,
+/// Heuristic search that keeps only the `width` best partial paths at
+/// each extension step instead of exploring every branch, trading
+/// exactness for speed on large components. Mirrors the shape of the
+/// existing Python `_beam_search_longest_path` fallback
+/// (`backend/solver/maze_solver.py`): seed the beam from low-degree nodes
+/// (likely path endpoints), then greedily extend and prune. Never reports
+/// `max_depth` exceeded, since it always keeps a bounded beam rather than
+/// exploring to exhaustion.
+struct BeamSearch {
+    width: usize,
 }
 
-#[pyfunction]
-pub fn process_and_solve_maze(py: Python, data: PyObject) -> PyResult<Vec<Vec<String>>> {
-    let total_start = Instant::now();
-    let data_str = data.extract::<String>(py)?;
-    
-    py.allow_threads(move || {
-        let maze_data: MazeData = serde_json::from_str(&data_str)
-            .map_err(|e| PyErr::new::<PyValueError, _>(format!("JSON error: {}", e)))?;
-        
-        println!("SOLVING: {} components", maze_data.components.len());
-        
-        // Process each component in parallel and collect results
-        let results: Vec<Vec<String>> = maze_data.components.par_iter()
-            .map(|component| {
-                // Sort neighbors clockwise for better performance
-                let sorted_component = sort_neighbors_clockwise(component);
-                let (graph, id_to_name) = build_graph_from_adjacency(&sorted_component);
-                
-                // Find the longest path using only the optimized approach
-                let mut path_ids = exact_longest_path_optimized(&graph);
-                
-                // Validate the path
-                if !validate_path(&graph, &path_ids) {
-                    println!("WARNING: Found invalid path: {:?}", path_ids);
-                    println!("Retrying algorithm once...");
-                    
-                    // Retry once
-                    path_ids = exact_longest_path_optimized(&graph);
-                    
-                    // Check again
-                    if !validate_path(&graph, &path_ids) {
-                        println!("ERROR: Still found invalid path after retry: {:?}", path_ids);
-                        panic!("Failed to find valid path after retry");
-                    } else {
-                        println!("Retry successful, found valid path");
+impl LongestPathStrategy for BeamSearch {
+    fn name(&self) -> &'static str {
+        "beam-search"
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &self,
+        graph: &Graph,
+        cancel: Option<&Arc<AtomicBool>>,
+        required: &[NodeId],
+        edge_weights: Option<&EdgeWeights>,
+        trace: Option<&SearchTrace>,
+        initial_best_weight: usize,
+        max_depth: usize,
+        _deterministic: bool,
+    ) -> (Vec<NodeId>, bool) {
+        let node_count = graph.node_count();
+        if node_count == 0 {
+            return (Vec::new(), false);
+        }
+
+        let mut start_nodes: Vec<NodeId> = graph.nodes().into_iter().filter(|&n| graph.get_neighbors(n).len() <= 2).collect();
+        if start_nodes.is_empty() {
+            start_nodes = graph.nodes();
+        }
+        start_nodes.truncate(15);
+
+        let mut best_weight = initial_best_weight;
+        let mut best_path: Vec<NodeId> = Vec::new();
+
+        for start in start_nodes {
+            if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+                break;
+            }
+
+            let mut start_visited = NodeBitset::new();
+            start_visited.set(start);
+            let mut beam: Vec<(Vec<NodeId>, usize, NodeBitset)> = vec![(vec![start], 1, start_visited)];
+
+            while !beam.is_empty() {
+                if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+                    break;
+                }
+
+                let mut candidates: Vec<(Vec<NodeId>, usize, NodeBitset)> = Vec::new();
+                for (path, weight, visited) in &beam {
+                    if path.len() >= max_depth {
+                        continue;
+                    }
+                    let &last = path.last().unwrap();
+                    for &neighbor in graph.get_neighbors(last) {
+                        if !visited.contains(neighbor) {
+                            let mut next_path = path.clone();
+                            next_path.push(neighbor);
+                            let mut next_visited = visited.clone();
+                            next_visited.set(neighbor);
+                            candidates.push((next_path, weight + edge_weight(edge_weights, last, neighbor), next_visited));
+                        }
                     }
                 }
-                
-                // Convert node IDs back to names
-                path_ids.iter().map(|&id| id_to_name[id as usize].clone()).collect()
-            })
-            .collect();
-        
-        println!("TOTAL TIME: {:?}", total_start.elapsed());
-        
-        Ok(results)
-    })
-}
+                if candidates.is_empty() {
+                    break;
+                }
 
-// Function to verify a path is valid (no duplicates, all edges exist)
-fn validate_path(graph: &Graph, path: &[NodeId]) -> bool {
-    if path.is_empty() {
-        return true;
+                candidates.sort_by_key(|c| std::cmp::Reverse(c.1));
+                candidates.truncate(self.width);
+
+                if let Some(t) = trace {
+                    t.record(&candidates[0].0);
+                }
+                for (path, weight, visited) in &candidates {
+                    if *weight > best_weight && required.iter().all(|&id| visited.contains(id)) {
+                        best_weight = *weight;
+                        best_path = path.clone();
+                    }
+                }
+
+                beam = candidates;
+            }
+        }
+
+        (best_path, false)
     }
-    
-    // Check for duplicates
-    let mut seen = HashSet::new();
-    for &node in path {
-        if !seen.insert(node) {
-            return false; // Duplicate found
+}
+
+/// Greedy min-degree elimination heuristic: repeatedly eliminate the
+/// remaining vertex with the fewest remaining neighbors, connecting its
+/// neighbors to each other before removing it (as chordal-completion during
+/// tree-decomposition construction would). The largest resulting "bag"
+/// (eliminated vertex plus its remaining neighbors at that point) minus one
+/// bounds the treewidth. Exact treewidth is NP-hard to compute; this is the
+/// standard fast estimate used to gate `TreewidthDp` below.
+fn estimated_treewidth(graph: &Graph) -> usize {
+    let mut remaining: HashMap<NodeId, HashSet<NodeId>> = graph
+        .nodes()
+        .into_iter()
+        .map(|n| (n, graph.get_neighbors(n).iter().copied().collect()))
+        .collect();
+
+    let mut max_bag = 0usize;
+    while !remaining.is_empty() {
+        let node = *remaining
+            .iter()
+            .min_by_key(|(_, neighbors)| neighbors.len())
+            .map(|(node, _)| node)
+            .unwrap();
+        let neighbors: Vec<NodeId> = remaining[&node].iter().copied().collect();
+        max_bag = max_bag.max(neighbors.len() + 1);
+
+        for &a in &neighbors {
+            for &b in &neighbors {
+                if a != b {
+                    remaining.get_mut(&a).unwrap().insert(b);
+                }
+            }
+        }
+        for &neighbor in &neighbors {
+            remaining.get_mut(&neighbor).unwrap().remove(&node);
         }
+        remaining.remove(&node);
     }
-    
-    // Check all edges exist
-    for i in 0..path.len()-1 {
-        let curr = path[i];
-        let next = path[i+1];
-        
-        if !graph.get_neighbors(curr).contains(&next) {
-            return false; // Non-adjacent nodes
+
+    max_bag.saturating_sub(1)
+}
+
+fn edge_count(graph: &Graph) -> usize {
+    graph.nodes().iter().map(|&n| graph.get_neighbors(n).len()).sum::<usize>() / 2
+}
+
+/// Exact O(n) longest path ("weighted tree diameter") for a tree: the
+/// farthest node from an arbitrary start is always one endpoint of the
+/// diameter, so the farthest node from *that* result is the other endpoint.
+/// Only valid when `graph` is actually a tree — callers must check that
+/// first, since this has no cycle handling at all.
+fn farthest_node(graph: &Graph, edge_weights: Option<&EdgeWeights>, from: NodeId) -> (NodeId, usize, HashMap<NodeId, NodeId>) {
+    let mut dist: HashMap<NodeId, usize> = HashMap::new();
+    let mut parent: HashMap<NodeId, NodeId> = HashMap::new();
+    dist.insert(from, 1);
+    let mut stack = vec![from];
+    let mut farthest = from;
+    let mut farthest_weight = 1usize;
+
+    while let Some(node) = stack.pop() {
+        let weight = dist[&node];
+        if weight > farthest_weight {
+            farthest_weight = weight;
+            farthest = node;
+        }
+        for &neighbor in graph.get_neighbors(node) {
+            if let Entry::Vacant(entry) = dist.entry(neighbor) {
+                entry.insert(weight + edge_weight(edge_weights, node, neighbor));
+                parent.insert(neighbor, node);
+                stack.push(neighbor);
+            }
         }
     }
-    
-    true
+
+    (farthest, farthest_weight, parent)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn longest_path_in_tree(graph: &Graph, edge_weights: Option<&EdgeWeights>, start: NodeId) -> (Vec<NodeId>, usize) {
+    let (one_end, _, _) = farthest_node(graph, edge_weights, start);
+    let (other_end, weight, parent) = farthest_node(graph, edge_weights, one_end);
 
-    #[test]
-    fn test_node_bitset_basic() {
-        let mut visited = NodeBitset::new();
-        assert_eq!(visited.contains(10), false);
-        visited.set(10);
-        assert_eq!(visited.contains(10), true);
-        visited.clear(10);
-        assert_eq!(visited.contains(10), false);
+    let mut path = vec![other_end];
+    while let Some(&p) = parent.get(path.last().unwrap()) {
+        path.push(p);
     }
-    
-    #[test]
-    fn test_node_bitset_multiple() {
-        let mut visited = NodeBitset::new();
-        for i in 0..100 {
-            visited.set(i);
+    path.reverse();
+    (path, weight)
+}
+
+/// Automatically-selected fast path for genuinely low-treewidth components,
+/// gated by `estimated_treewidth`. Only treewidth 1 (a tree/forest — no
+/// extra edges beyond a spanning tree, which is common right after a maze
+/// is carved and before shortcuts are added) has an O(n) exact algorithm
+/// implemented so far (`longest_path_in_tree`, a weighted tree-diameter
+/// computation); anything wider, or carrying `required` waypoints the tree
+/// algorithm doesn't account for, falls back to `ExactBacktracking` —
+/// still exact, just without the DP speedup. General treewidth-k DP and
+/// `branch-and-bound` remain future registry entries (see
+/// `strategy_by_name`).
+struct TreewidthDp {
+    threshold: usize,
+}
+
+impl LongestPathStrategy for TreewidthDp {
+    fn name(&self) -> &'static str {
+        "dp-treewidth"
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &self,
+        graph: &Graph,
+        cancel: Option<&Arc<AtomicBool>>,
+        required: &[NodeId],
+        edge_weights: Option<&EdgeWeights>,
+        trace: Option<&SearchTrace>,
+        initial_best_weight: usize,
+        max_depth: usize,
+        deterministic: bool,
+    ) -> (Vec<NodeId>, bool) {
+        let node_count = graph.node_count();
+        let is_tree = node_count > 0 && edge_count(graph) == node_count - 1;
+        if required.is_empty() && is_tree && estimated_treewidth(graph) <= self.threshold {
+            // No incremental "thinking" trace here: the tree-diameter
+            // computation below is a direct O(n) result, not an
+            // exploration process with intermediate candidate paths.
+            let start = graph.nodes()[0];
+            let (path, _weight) = longest_path_in_tree(graph, edge_weights, start);
+            return (path, false);
         }
-        assert_eq!(visited.count(), 100);
-        
-        for i in 0..100 {
-            assert_eq!(visited.contains(i), true);
+        ExactBacktracking.search(graph, cancel, required, edge_weights, trace, initial_best_weight, max_depth, deterministic)
+    }
+}
+
+/// Resolves a strategy name to an implementation, defaulting unknown names
+/// to `ExactBacktracking` so a typo degrades to the safe, exact behavior
+/// rather than silently changing which component fails. `dp-treewidth`
+/// currently only accelerates the treewidth-1 (tree) case; wider components
+/// fall through to exact backtracking. `branch-and-bound` (tighter-pruning
+/// exhaustive search) is a known future registry entry, not implemented yet.
+pub(crate) fn strategy_by_name(name: Option<&str>) -> Box<dyn LongestPathStrategy> {
+    match name {
+        Some("beam-search") => Box::new(BeamSearch { width: 64 }),
+        Some("dp-treewidth") => Box::new(TreewidthDp { threshold: 1 }),
+        _ => Box::new(ExactBacktracking),
+    }
+}
+
+/// Pure-Rust longest-path solve for a single component, bypassing PyO3
+/// entirely. Used only by `benches/solver_benchmarks.rs` via
+/// `bench_support`, so the benchmark binary can drive the search without an
+/// embedded Python interpreter or a `MazeData` payload. `ordering` lets the
+/// benchmark compare `sort_neighbors`'s heuristics against each other on the
+/// same fixture.
+pub(crate) fn bench_solve_longest_path(
+    component: &HashMap<String, Vec<String>>,
+    strategy: Option<&str>,
+    ordering: NeighborOrdering,
+) -> usize {
+    let sorted = sort_neighbors(component, ordering, None);
+    let (graph, _) = build_graph_from_adjacency(&sorted);
+    let strategy_impl = strategy_by_name(strategy);
+    let (path, _) = strategy_impl.search(&graph, None, &[], None, None, 0, MAX_NODE_COUNT, false);
+    path.len()
+}
+
+fn graph_is_connected(graph: &Graph) -> bool {
+    let nodes = graph.nodes();
+    let Some(&start) = nodes.first() else { return true };
+
+    let mut visited = NodeBitset::new();
+    visited.set(start);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for &neighbor in graph.get_neighbors(node) {
+            if !visited.contains(neighbor) {
+                visited.set(neighbor);
+                stack.push(neighbor);
+            }
         }
-        
-        for i in 100..200 {
-            assert_eq!(visited.contains(i), false);
+    }
+
+    nodes.iter().all(|&n| visited.contains(n))
+}
+
+/// Cheap checks that rule out a Hamiltonian path without exhaustive search:
+/// the graph must be connected, and a degree-1 node can only ever be a path
+/// endpoint (it has just one edge to enter or leave on), so more than two of
+/// them makes a Hamiltonian path impossible.
+fn hamiltonian_necessary_conditions_pass(graph: &Graph) -> bool {
+    if graph.node_count() == 0 {
+        return true;
+    }
+    if !graph_is_connected(graph) {
+        return false;
+    }
+    let degree_one_count = graph
+        .nodes()
+        .iter()
+        .filter(|&&n| graph.get_neighbors(n).len() == 1)
+        .count();
+    degree_one_count <= 2
+}
+
+/// Held-Karp style subset DP deciding whether a Hamiltonian path exists.
+/// `dp[mask]` is a bitmask of the nodes that can end a path visiting exactly
+/// the nodes in `mask`. Runs in `O(2^n * n)` time and `2^n * 4` bytes, so
+/// it's only used below `MAX_HAMILTONIAN_DP_NODES`.
+const MAX_HAMILTONIAN_DP_NODES: usize = 24;
+
+fn hamiltonian_path_exists_dp(graph: &Graph) -> bool {
+    let nodes = graph.nodes();
+    let n = nodes.len();
+    if n <= 1 {
+        return true;
+    }
+
+    let index_of: HashMap<NodeId, usize> = nodes.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+    let adjacency: Vec<u32> = nodes
+        .iter()
+        .map(|&id| {
+            graph
+                .get_neighbors(id)
+                .iter()
+                .fold(0u32, |mask, &neighbor| mask | (1u32 << index_of[&neighbor]))
+        })
+        .collect();
+
+    let full: u32 = (1u32 << n) - 1;
+    let mut dp = vec![0u32; 1 << n];
+    for v in 0..n {
+        dp[1usize << v] |= 1u32 << v;
+    }
+
+    for mask in 1u32..=full {
+        let mut endpoints = dp[mask as usize];
+        while endpoints != 0 {
+            let v = endpoints.trailing_zeros() as usize;
+            endpoints &= endpoints - 1;
+
+            let mut candidates = adjacency[v] & !mask;
+            while candidates != 0 {
+                let u = candidates.trailing_zeros() as usize;
+                candidates &= candidates - 1;
+                dp[(mask | (1u32 << u)) as usize] |= 1u32 << u;
+            }
+        }
+    }
+
+    dp[full as usize] != 0
+}
+
+/// Whether a component has a Hamiltonian path: `Some(true)`/`Some(false)`
+/// when that's known for certain, or `None` when the component is too large
+/// for the exact subset DP and passed the cheap necessary-condition checks
+/// (i.e. genuinely undetermined, not "probably yes").
+fn hamiltonian_path_status(graph: &Graph) -> Option<bool> {
+    if !hamiltonian_necessary_conditions_pass(graph) {
+        return Some(false);
+    }
+    if graph.node_count() <= MAX_HAMILTONIAN_DP_NODES {
+        Some(hamiltonian_path_exists_dp(graph))
+    } else {
+        None
+    }
+}
+
+/// A graph with long degree-2 chains collapsed into single weighted edges,
+/// plus enough bookkeeping to expand a path found on it back to the
+/// original node sequence.
+///
+/// The exponential backtracking search branches on every node it visits, so
+/// a chain of degree-2 corridor cells (common in generated hex mazes)
+/// multiplies the search space without ever offering a real choice. Nodes
+/// in `keep` (typically `must_visit` targets) are never contracted away
+/// since the search needs to consider them explicitly.
+struct Contraction {
+    graph: Graph,
+    edge_weights: EdgeWeights,
+    /// Original-node ids swallowed by each contracted edge, keyed the same
+    /// direction as the edge, in path order from the edge's `from` side.
+    interior: HashMap<(NodeId, NodeId), Vec<NodeId>>,
+}
+
+fn contract_degree2_chains(graph: &Graph, keep: &NodeBitset) -> Contraction {
+    let is_junction = |node: NodeId| graph.get_neighbors(node).len() != 2 || keep.contains(node);
+
+    let mut contracted = Graph::new();
+    let mut edge_weights = EdgeWeights::new();
+    let mut interior: HashMap<(NodeId, NodeId), Vec<NodeId>> = HashMap::new();
+    let mut walked: HashSet<(NodeId, NodeId)> = HashSet::new();
+
+    for &start in graph.nodes().iter() {
+        if !is_junction(start) {
+            continue;
+        }
+        let _ = contracted.add_node(start);
+
+        for &first_step in graph.get_neighbors(start) {
+            if walked.contains(&(start, first_step)) {
+                continue;
+            }
+
+            let mut prev = start;
+            let mut current = first_step;
+            let mut chain = Vec::new();
+            while !is_junction(current) {
+                walked.insert((prev, current));
+                walked.insert((current, prev));
+                let next = graph
+                    .get_neighbors(current)
+                    .iter()
+                    .copied()
+                    .find(|&n| n != prev)
+                    .unwrap_or(prev);
+                chain.push(current);
+                prev = current;
+                current = next;
+            }
+            walked.insert((prev, current));
+            walked.insert((current, prev));
+
+            let end = current;
+            let weight = chain.len() + 1;
+            let _ = contracted.add_node(end);
+            let _ = contracted.add_edge(start, end);
+            edge_weights.insert((start, end), weight);
+            interior.insert((start, end), chain.clone());
+
+            chain.reverse();
+            edge_weights.insert((end, start), weight);
+            interior.insert((end, start), chain);
+        }
+    }
+
+    Contraction { graph: contracted, edge_weights, interior }
+}
+
+/// Expands a path found on a contracted graph back to the original node
+/// sequence by splicing each edge's swallowed interior nodes back in. A
+/// no-op (returns `path` unchanged) when `interior` is empty, so callers can
+/// use it unconditionally regardless of whether contraction actually ran.
+fn expand_contracted_path(path: &[NodeId], interior: &HashMap<(NodeId, NodeId), Vec<NodeId>>) -> Vec<NodeId> {
+    if path.is_empty() {
+        return Vec::new();
+    }
+
+    let mut expanded = Vec::with_capacity(path.len());
+    expanded.push(path[0]);
+    for pair in path.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        if let Some(nodes) = interior.get(&(from, to)) {
+            expanded.extend(nodes.iter().copied());
+        }
+        expanded.push(to);
+    }
+    expanded
+}
+
+/// Checks whether every not-yet-visited node in `required` can still be
+/// reached from `current` through unvisited nodes, used to prune branches
+/// of the search that can no longer satisfy a `must_visit` list.
+fn required_reachable(graph: &Graph, current: NodeId, visited: &NodeBitset, required: &[NodeId]) -> bool {
+    let mut reached = NodeBitset::new();
+    reached.set(current);
+    let mut stack = vec![current];
+
+    while let Some(node) = stack.pop() {
+        for &neighbor in graph.get_neighbors(node) {
+            if !visited.contains(neighbor) && !reached.contains(neighbor) {
+                reached.set(neighbor);
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    required.iter().all(|&id| visited.contains(id) || reached.contains(id))
+}
+
+/// An optimistic upper bound on how much further `path_weight` could grow
+/// from `current`: one for every unvisited node still reachable, plus the
+/// extra original-node count any reachable edge could contribute if it were
+/// a contracted chain. It never undercounts the true best case, so branches
+/// where `path_weight + this bound` can't beat the current best can be
+/// pruned without ever discarding the actual best path.
+fn optimistic_remaining_bound(
+    graph: &Graph,
+    current: NodeId,
+    visited: &NodeBitset,
+    edge_weights: Option<&EdgeWeights>,
+) -> usize {
+    let mut reached = NodeBitset::new();
+    reached.set(current);
+    let mut stack = vec![current];
+    let mut counted_edges: HashSet<(NodeId, NodeId)> = HashSet::new();
+    let mut bound = 0usize;
+
+    while let Some(node) = stack.pop() {
+        for &neighbor in graph.get_neighbors(node) {
+            if visited.contains(neighbor) {
+                continue;
+            }
+            let key = if node < neighbor { (node, neighbor) } else { (neighbor, node) };
+            if counted_edges.insert(key) {
+                bound += edge_weight(edge_weights, node, neighbor).saturating_sub(1);
+            }
+            if !reached.contains(neighbor) {
+                reached.set(neighbor);
+                bound += 1;
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    bound
+}
+
+/// One in-progress node in the explicit DFS stack used by
+/// `backtrack_exact_standard_optimized`: which node it's exploring, how far
+/// into that node's neighbor list it's gotten, its path weight at the point
+/// it was entered, and whether the one-time "on enter" checks (trace
+/// sampling, best-weight update, pruning) have already run for it.
+struct SearchFrame {
+    node: NodeId,
+    next_neighbor: usize,
+    weight: usize,
+    entered: bool,
+}
+
+/// Explicit-stack depth-first search for the longest path, equivalent to
+/// (and replacing) a recursive backtracker. Recursion here would grow one
+/// native stack frame per path node, which can approach `MAX_NODE_COUNT`
+/// deep on large mazes and risks overflowing a thread's stack on platforms
+/// with small default stack sizes; a heap-allocated `Vec<SearchFrame>` has
+/// no such limit. `max_depth` additionally caps how deep the search is
+/// allowed to go at all, setting `depth_exceeded` (checked by the caller,
+/// which turns it into a clean `SolverError` instead of an unbounded
+/// exploration) rather than growing the path further.
+#[allow(clippy::too_many_arguments)]
+fn backtrack_exact_standard_optimized(
+    graph: &Graph,
+    path: &mut Vec<NodeId>,
+    path_weight: usize,
+    visited: &mut NodeBitset,
+    best_weight: &mut usize,
+    best_path: &mut Vec<NodeId>,
+    cancel: Option<&Arc<AtomicBool>>,
+    required: &[NodeId],
+    edge_weights: Option<&EdgeWeights>,
+    trace: Option<&SearchTrace>,
+    max_depth: usize,
+    depth_exceeded: &AtomicBool,
+) {
+    let mut stack: Vec<SearchFrame> = Vec::with_capacity(max_depth.min(MAX_NODE_COUNT));
+    stack.push(SearchFrame { node: *path.last().unwrap(), next_neighbor: 0, weight: path_weight, entered: false });
+
+    while let Some(top) = stack.len().checked_sub(1) {
+        // Checked on every frame rather than every N: this stays cheap
+        // relative to the branch factor work below, and it guarantees a
+        // cancelled solve unwinds fast.
+        if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+            return;
+        }
+
+        let node = stack[top].node;
+        let weight = stack[top].weight;
+
+        if !stack[top].entered {
+            stack[top].entered = true;
+
+            if let Some(t) = trace {
+                t.record(path);
+            }
+
+            let required_satisfied = required.iter().all(|&id| visited.contains(id));
+            if required_satisfied && weight > *best_weight {
+                *best_weight = weight;
+                best_path.clear();
+                best_path.extend_from_slice(path);
+            }
+
+            // Prune: a required node can no longer be reached without
+            // revisiting nodes already on the path, or even the optimistic
+            // best case from here can't beat the current best.
+            let pruned = (!required_satisfied && !required_reachable(graph, node, visited, required))
+                || (*best_weight > 0 && weight + optimistic_remaining_bound(graph, node, visited, edge_weights) <= *best_weight);
+
+            if pruned {
+                pop_frame(&mut stack, path, visited, node);
+                continue;
+            }
+        }
+
+        if path.len() >= max_depth {
+            // Only a branch that could still grow is cut short by the limit.
+            let neighbors = graph.get_neighbors(node);
+            if (neighbor_visited_mask(neighbors, visited).count_ones() as usize) < neighbors.len() {
+                depth_exceeded.store(true, Ordering::Relaxed);
+            }
+            pop_frame(&mut stack, path, visited, node);
+            continue;
+        }
+
+        let neighbors = graph.get_neighbors(node);
+        let visited_mask = neighbor_visited_mask(neighbors, visited);
+        let mut next_child = None;
+        while stack[top].next_neighbor < neighbors.len() {
+            let i = stack[top].next_neighbor;
+            let neighbor = neighbors[i];
+            stack[top].next_neighbor += 1;
+            if visited_mask & (1 << i) == 0 {
+                next_child = Some(neighbor);
+                break;
+            }
+        }
+
+        match next_child {
+            Some(neighbor) => {
+                visited.set(neighbor);
+                path.push(neighbor);
+                stack.push(SearchFrame {
+                    node: neighbor,
+                    next_neighbor: 0,
+                    weight: weight + edge_weight(edge_weights, node, neighbor),
+                    entered: false,
+                });
+            }
+            None => pop_frame(&mut stack, path, visited, node),
+        }
+    }
+}
+
+/// Pops the top DFS frame and, unless it was the root (whose node the
+/// caller of `backtrack_exact_standard_optimized` owns and undoes itself),
+/// undoes the `path`/`visited` mutation made when it was pushed as a child.
+fn pop_frame(stack: &mut Vec<SearchFrame>, path: &mut Vec<NodeId>, visited: &mut NodeBitset, node: NodeId) {
+    stack.pop();
+    if !stack.is_empty() {
+        path.pop();
+        visited.clear(node);
+    }
+}
+
+// Data structures for deserialization
+#[derive(Deserialize)]
+pub(crate) struct MazeData {
+    pub(crate) components: Vec<HashMap<String, Vec<String>>>,
+    /// Optional per-component list of node names that a valid path must
+    /// include (e.g. "collect all keys"), indexed the same as `components`.
+    /// Missing or short lists mean "no requirement" for those components.
+    #[serde(default)]
+    pub(crate) must_visit: Vec<Vec<String>>,
+    /// Optional per-component previously-known-good path, indexed the same
+    /// as `components`. Used as a warm-start lower bound so the search
+    /// prunes against it immediately instead of rediscovering it from
+    /// scratch; repaired against the current graph if the maze changed
+    /// since the path was recorded (see `repair_warm_start`).
+    #[serde(default)]
+    pub(crate) warm_start: Vec<Vec<String>>,
+    /// Schema version of this payload, checked against
+    /// `maze_schema::CURRENT_SCHEMA_VERSION`. Omitted by callers written
+    /// before this field existed, which are treated as version 1.
+    #[serde(default, rename = "schemaVersion")]
+    pub(crate) schema_version: Option<u32>,
+    /// Optional per-component `[start, end]` pair selecting shortest-path
+    /// mode for that component (see `shortest_path` on
+    /// `process_and_solve_maze`), indexed the same as `components`. Missing
+    /// entries leave that component on the default longest-path search.
+    #[serde(default)]
+    pub(crate) endpoints: Vec<Vec<String>>,
+    /// Optional per-component axial `(q, r)` coordinates, keyed by node
+    /// name, indexed the same as `components`. Always checked against the
+    /// adjacency by `maze_schema` validation; used for neighbor ordering only
+    /// when `neighbor_ordering` is `"clockwise-coords"`, where missing entries
+    /// fall back to `ClockwiseNumeric` for that component.
+    #[serde(default)]
+    pub(crate) coords: Vec<HashMap<String, (i32, i32)>>,
+}
+
+/// Walks a previously-known-good path against the current graph and
+/// truncates it at the first node name that no longer exists, the first
+/// repeated node, or the first step that's no longer an edge — so an
+/// edited maze can still use as much of a stale warm-start path as remains
+/// valid, rather than rejecting it outright.
+fn repair_warm_start(graph: &Graph, name_to_id: &HashMap<&str, NodeId>, names: &[String]) -> Vec<NodeId> {
+    let mut repaired = Vec::with_capacity(names.len());
+    let mut visited = NodeBitset::new();
+
+    for name in names {
+        let Some(&id) = name_to_id.get(name.as_str()) else { break };
+        if visited.contains(id) {
+            break;
+        }
+        if let Some(&prev) = repaired.last() {
+            if !graph.get_neighbors(prev).contains(&id) {
+                break;
+            }
+        }
+        visited.set(id);
+        repaired.push(id);
+    }
+
+    repaired
+}
+
+/// Finds a shortest path between `start` and `end` by expanding BFS
+/// frontiers from both ends and stopping as soon as they meet in the
+/// middle. For a graph with branching factor `b` and distance `d` this
+/// explores roughly `2*b^(d/2)` nodes instead of the `b^d` a one-sided BFS
+/// would, which matters on the large hex mazes this solver targets.
+fn bidirectional_shortest_path(graph: &Graph, start: NodeId, end: NodeId) -> Option<Vec<NodeId>> {
+    if start == end {
+        return Some(vec![start]);
+    }
+
+    let mut forward_parent: HashMap<NodeId, NodeId> = HashMap::new();
+    let mut backward_parent: HashMap<NodeId, NodeId> = HashMap::new();
+    let mut forward_frontier = vec![start];
+    let mut backward_frontier = vec![end];
+    let mut forward_visited = NodeBitset::new();
+    let mut backward_visited = NodeBitset::new();
+    forward_visited.set(start);
+    backward_visited.set(end);
+    let mut meeting_node = None;
+
+    while !forward_frontier.is_empty() && !backward_frontier.is_empty() && meeting_node.is_none() {
+        // Always expand whichever frontier is smaller, so the two searches
+        // stay balanced instead of one side doing all the work.
+        let expand_forward = forward_frontier.len() <= backward_frontier.len();
+        let (frontier, visited, other_visited, parent) = if expand_forward {
+            (&mut forward_frontier, &mut forward_visited, &backward_visited, &mut forward_parent)
+        } else {
+            (&mut backward_frontier, &mut backward_visited, &forward_visited, &mut backward_parent)
+        };
+
+        let mut next = Vec::new();
+        'expand: for &node in frontier.iter() {
+            for &neighbor in graph.get_neighbors(node) {
+                if other_visited.contains(neighbor) {
+                    parent.entry(neighbor).or_insert(node);
+                    meeting_node = Some(neighbor);
+                    break 'expand;
+                }
+                if !visited.contains(neighbor) {
+                    visited.set(neighbor);
+                    parent.insert(neighbor, node);
+                    next.push(neighbor);
+                }
+            }
+        }
+        *frontier = next;
+    }
+
+    let meeting = meeting_node?;
+
+    let mut path = vec![meeting];
+    let mut node = meeting;
+    while let Some(&parent) = forward_parent.get(&node) {
+        path.push(parent);
+        node = parent;
+    }
+    path.reverse();
+
+    let mut node = meeting;
+    while let Some(&parent) = backward_parent.get(&node) {
+        path.push(parent);
+        node = parent;
+    }
+
+    Some(path)
+}
+
+/// A single stop along a simplified waypoint path, annotated with the turn
+/// taken to arrive at it (relative to the previous straight run).
+#[derive(Debug, Clone)]
+struct Waypoint {
+    cell: String,
+    turn: &'static str,
+}
+
+fn waypoint_to_dict(py: Python<'_>, waypoint: &Waypoint) -> PyObject {
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("cell", &waypoint.cell).unwrap();
+    dict.set_item("turn", waypoint.turn).unwrap();
+    dict.into_any().unbind()
+}
+
+/// Collapses collinear runs in a path of node names on the hex lattice.
+///
+/// Node names are the same linear indices used by `sort_neighbors_clockwise`,
+/// so a constant step between consecutive names indicates travel in a
+/// straight line. Waypoints are kept at the path endpoints and at every
+/// point where the step changes (a turn); everything else is redundant for
+/// rendering purposes.
+fn simplify_waypoints(path_names: &[String]) -> Vec<Waypoint> {
+    if path_names.len() <= 2 {
+        return path_names
+            .iter()
+            .map(|cell| Waypoint { cell: cell.clone(), turn: "straight" })
+            .collect();
+    }
+
+    let indices: Vec<isize> = path_names
+        .iter()
+        .map(|s| s.parse::<isize>().unwrap_or(0))
+        .collect();
+
+    let mut waypoints = Vec::with_capacity(path_names.len());
+    waypoints.push(Waypoint { cell: path_names[0].clone(), turn: "start" });
+
+    let mut prev_step = indices[1] - indices[0];
+    for i in 1..path_names.len() - 1 {
+        let step = indices[i + 1] - indices[i];
+        if step != prev_step {
+            waypoints.push(Waypoint { cell: path_names[i].clone(), turn: "turn" });
+            prev_step = step;
+        }
+    }
+
+    waypoints.push(Waypoint { cell: path_names.last().unwrap().clone(), turn: "end" });
+    waypoints
+}
+
+/// Per-component solve results, keyed by a hash of everything that affects
+/// them (`data` plus the flags that change what gets computed, but not
+/// `simplify_waypoints`, which is derived from `cells` after the fact).
+/// `max_path_depth` and `component_timeout_ms` are not part of the key, so a
+/// cached result is returned even where this call's depth limit or time
+/// budget would have stopped a fresh search early. The fifth field is
+/// `Some(warning)` for a component skipped by `lenient` mode (empty
+/// cells/trace/hamiltonian/strategy alongside it), `None` otherwise.
+type CachedResults = Vec<(Vec<String>, Vec<Vec<String>>, Option<bool>, Option<&'static str>, Option<String>, bool, u64)>;
+
+static SOLVE_CACHE: OnceLock<Mutex<HashMap<u64, CachedResults>>> = OnceLock::new();
+
+fn solve_cache() -> &'static Mutex<HashMap<u64, CachedResults>> {
+    SOLVE_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn solve_cache_key(
+    data_str: &str,
+    trace_every: Option<usize>,
+    check_hamiltonian: bool,
+    shortest_path: bool,
+    search_strategy: Option<&str>,
+    lenient: bool,
+    cycle: bool,
+    deterministic: bool,
+    neighbor_ordering: Option<&str>,
+    neighbor_ordering_seed: Option<u64>,
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data_str.hash(&mut hasher);
+    trace_every.hash(&mut hasher);
+    check_hamiltonian.hash(&mut hasher);
+    shortest_path.hash(&mut hasher);
+    search_strategy.hash(&mut hasher);
+    lenient.hash(&mut hasher);
+    cycle.hash(&mut hasher);
+    deterministic.hash(&mut hasher);
+    neighbor_ordering.hash(&mut hasher);
+    neighbor_ordering_seed.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Drops every cached solve result. Exposed to Python so long-running
+/// services can bound memory growth and tests can force a fresh solve
+/// instead of observing a result cached by an earlier test.
+#[pyfunction]
+pub fn clear_solver_cache() {
+    solve_cache().lock().unwrap().clear();
+}
+
+/// Reports the number of cached solve results, for Python-side memory
+/// monitoring.
+#[pyfunction]
+pub fn solver_cache_stats(py: Python) -> PyObject {
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("entries", solve_cache().lock().unwrap().len()).unwrap();
+    dict.into_any().unbind()
+}
+
+/// Watches `timeout_ms` (if set) on a background thread and stops the
+/// search early once it elapses, without disturbing an already-cancelled
+/// call: the returned `should_stop` flag is set on either the deadline
+/// firing or the caller's own `cancel_flag` firing, so a single flag can be
+/// threaded through the rest of `solve_component`'s search calls, while
+/// `timed_out` is set only by the deadline itself — letting the caller tell
+/// "the budget ran out" (return the best-so-far path as partial) apart from
+/// "the user cancelled" (return nothing, the existing behavior).
+fn spawn_component_timeout(
+    cancel_flag: Option<&Arc<AtomicBool>>,
+    timeout_ms: Option<u64>,
+) -> (Option<Arc<AtomicBool>>, Option<Arc<AtomicBool>>) {
+    let Some(timeout_ms) = timeout_ms else {
+        return (cancel_flag.cloned(), None);
+    };
+
+    let should_stop = Arc::new(AtomicBool::new(false));
+    let timed_out = Arc::new(AtomicBool::new(false));
+    let should_stop_clone = should_stop.clone();
+    let timed_out_clone = timed_out.clone();
+    let user_cancel = cancel_flag.cloned();
+    let deadline = Duration::from_millis(timeout_ms);
+
+    thread::spawn(move || {
+        let start = Instant::now();
+        while start.elapsed() < deadline {
+            if user_cancel.as_ref().is_some_and(|c| c.load(Ordering::Relaxed)) {
+                should_stop_clone.store(true, Ordering::Relaxed);
+                return;
+            }
+            thread::sleep(Duration::from_millis(20).min(deadline.saturating_sub(start.elapsed())));
+        }
+        timed_out_clone.store(true, Ordering::Relaxed);
+        should_stop_clone.store(true, Ordering::Relaxed);
+    });
+
+    (Some(should_stop), Some(timed_out))
+}
+
+/// A single component's solved path, trace samples, Hamiltonian status,
+/// search strategy name, whether the result is only partial (cancelled or
+/// timed out), and elapsed time — `solve_component`'s return shape before
+/// `process_and_solve_mazes` tags it with a `maze_index`/wraps it into a
+/// `CachedResults` entry.
+type ComponentResult = (Vec<String>, Vec<Vec<String>>, Option<bool>, Option<&'static str>, bool, u64);
+
+/// Solves a single component: builds its graph, runs the appropriate search
+/// (bidirectional shortest-path, longest cycle, or exhaustive longest-path),
+/// and converts the result back to node names. Shared by
+/// `process_and_solve_maze` and `process_and_solve_mazes` so both
+/// single-maze and batch calls run the exact same per-component logic.
+#[allow(clippy::too_many_arguments)]
+fn solve_component(
+    maze_data: &MazeData,
+    index: usize,
+    component: &HashMap<String, Vec<String>>,
+    cancel_flag: Option<&Arc<AtomicBool>>,
+    trace_every: Option<usize>,
+    check_hamiltonian: bool,
+    shortest_path: bool,
+    max_depth: usize,
+    search_strategy: Option<&str>,
+    cycle: bool,
+    deterministic: bool,
+    timeout_ms: Option<u64>,
+    neighbor_ordering: NeighborOrdering,
+) -> Result<ComponentResult, SolverError> {
+    let component_start = Instant::now();
+    let (effective_cancel, timed_out) = spawn_component_timeout(cancel_flag, timeout_ms);
+    let cancel_flag = effective_cancel.as_ref();
+    let elapsed_ms = |start: Instant| start.elapsed().as_millis() as u64;
+
+    let coords = maze_data.coords.get(index);
+    let sorted_component = sort_neighbors(component, neighbor_ordering, coords);
+    let (graph, id_to_name) = build_graph_from_adjacency(&sorted_component);
+
+    if id_to_name.len() > MAX_NODE_COUNT {
+        return Err(SolverError::TooManyNodes {
+            component: index,
+            count: id_to_name.len(),
+            max: MAX_NODE_COUNT,
+        });
+    }
+
+    if cycle {
+        let (mut path_ids, depth_exceeded) = exact_longest_cycle_optimized(&graph, cancel_flag, max_depth, deterministic);
+        if depth_exceeded {
+            return Err(SolverError::PathDepthExceeded { component: index, limit: max_depth });
+        }
+        // Repeat the start node so callers see an explicit closed loop
+        // rather than having to infer the closing edge themselves.
+        if let (Some(&first), Some(&last)) = (path_ids.first(), path_ids.last()) {
+            if first != last {
+                path_ids.push(first);
+            }
+        }
+        let path_names = path_ids.iter().map(|&id| id_to_name[id as usize].clone()).collect();
+        let hamiltonian = check_hamiltonian.then(|| hamiltonian_path_status(&graph)).flatten();
+        // Set on either a timeout or a caller cancel, so neither kind of
+        // early stop gets cached as a complete answer.
+        let partial = cancel_flag.is_some_and(|c| c.load(Ordering::Relaxed));
+        return Ok((path_names, Vec::new(), hamiltonian, Some("longest-cycle"), partial, elapsed_ms(component_start)));
+    }
+
+    let name_to_id: HashMap<&str, NodeId> = id_to_name
+        .iter()
+        .enumerate()
+        .map(|(id, name)| (name.as_str(), id as NodeId))
+        .collect();
+    let required: Vec<NodeId> = maze_data.must_visit
+        .get(index)
+        .map(|names| names.iter().filter_map(|n| name_to_id.get(n.as_str()).copied()).collect())
+        .unwrap_or_default();
+
+    let endpoints: Option<(NodeId, NodeId)> = shortest_path
+        .then(|| maze_data.endpoints.get(index))
+        .flatten()
+        .filter(|names| names.len() == 2)
+        .and_then(|names| {
+            let start = name_to_id.get(names[0].as_str()).copied();
+            let end = name_to_id.get(names[1].as_str()).copied();
+            start.zip(end)
+        });
+
+    if let Some((start, end)) = endpoints {
+        let path_ids = bidirectional_shortest_path(&graph, start, end).unwrap_or_default();
+        let path_names = path_ids.iter().map(|&id| id_to_name[id as usize].clone()).collect();
+        return Ok((path_names, Vec::new(), None, Some("bidirectional-bfs"), false, elapsed_ms(component_start)));
+    }
+
+    let mut keep = NodeBitset::new();
+    for &id in &required {
+        keep.set(id);
+    }
+    let contraction = contract_degree2_chains(&graph, &keep);
+    // Degenerate graphs (a single junction, or a cycle with no
+    // junction at all) contract down to almost nothing useful;
+    // fall back to searching the original graph in that case.
+    let (search_graph, edge_weights) = if contraction.graph.node_count() >= 2 {
+        (&contraction.graph, Some(&contraction.edge_weights))
+    } else {
+        (&graph, None)
+    };
+
+    let trace = trace_every.filter(|&n| n > 0).map(SearchTrace::new);
+
+    let warm_start = maze_data.warm_start
+        .get(index)
+        .map(|names| repair_warm_start(&graph, &name_to_id, names))
+        .unwrap_or_default();
+    let warm_start_weight = warm_start.len();
+    let strategy_impl = strategy_by_name(search_strategy);
+
+    // Find the longest path using the selected strategy
+    let (mut path_ids, depth_exceeded) = strategy_impl.search(
+        search_graph, cancel_flag, &required, edge_weights, trace.as_ref(), warm_start_weight, max_depth, deterministic
+    );
+    if depth_exceeded {
+        return Err(SolverError::PathDepthExceeded { component: index, limit: max_depth });
+    }
+    path_ids = expand_contracted_path(&path_ids, &contraction.interior);
+
+    // The search only records a path when it beats the warm-start
+    // bound; if nothing did, the (already-valid) repaired
+    // warm-start path is itself the best known answer.
+    if path_ids.is_empty() && !warm_start.is_empty() {
+        path_ids = warm_start.clone();
+    }
+
+    let timed_out = timed_out.as_ref().is_some_and(|f| f.load(Ordering::Relaxed));
+    if cancel_flag.is_some_and(|c| c.load(Ordering::Relaxed)) && !timed_out {
+        println!("Solve cancelled by caller");
+        // `partial = true` here (not `false`) is load-bearing: it's what tells
+        // `solve_maze_core`'s cache-insert guard to skip caching this empty,
+        // incomplete result. A cancelled solve is not a real answer and must
+        // never be served back to a future caller with the same maze+flags.
+        return Ok((Vec::new(), Vec::new(), None, None, true, elapsed_ms(component_start)));
+    }
+
+    // Validate the path
+    if !validate_path(&graph, &path_ids) {
+        println!("WARNING: Found invalid path: {:?}", path_ids);
+        println!("Retrying algorithm once...");
+
+        // Retry once
+        let (retried_path_ids, retry_depth_exceeded) = strategy_impl.search(
+            search_graph, cancel_flag, &required, edge_weights, trace.as_ref(), warm_start_weight, max_depth, deterministic
+        );
+        if retry_depth_exceeded {
+            return Err(SolverError::PathDepthExceeded { component: index, limit: max_depth });
+        }
+        path_ids = expand_contracted_path(&retried_path_ids, &contraction.interior);
+        if path_ids.is_empty() && !warm_start.is_empty() {
+            path_ids = warm_start.clone();
+        }
+
+        // Check again
+        if !validate_path(&graph, &path_ids) {
+            println!("ERROR: Still found invalid path after retry: {:?}", path_ids);
+            return Err(SolverError::InvalidPathAfterRetry { component: index });
+        } else {
+            println!("Retry successful, found valid path");
+        }
+    }
+
+    // Convert node IDs back to names
+    let path_names = path_ids.iter().map(|&id| id_to_name[id as usize].clone()).collect();
+    let trace_names = trace
+        .map(|t| {
+            t.into_samples()
+                .iter()
+                .map(|sample| expand_contracted_path(sample, &contraction.interior)
+                    .iter()
+                    .map(|&id| id_to_name[id as usize].clone())
+                    .collect())
+                .collect()
+        })
+        .unwrap_or_default();
+    let hamiltonian = check_hamiltonian.then(|| hamiltonian_path_status(&graph)).flatten();
+    let strategy = Some(strategy_impl.name());
+
+    Ok((path_names, trace_names, hamiltonian, strategy, timed_out, elapsed_ms(component_start)))
+}
+
+/// Everything `process_and_solve_maze` does once the GIL can be released:
+/// cache lookup, parsing, schema validation and the parallel per-component
+/// solve, with cache insertion on a successful (non-partial) compute.
+/// Holds no `Python<'_>` and touches no Python objects, so both the
+/// blocking sync entry point (via `py.allow_threads`) and the async entry
+/// point (from a plain `rayon::spawn` worker thread with no GIL at all) can
+/// call it identically.
+#[allow(clippy::too_many_arguments)]
+fn solve_maze_core(
+    data_str: String,
+    cancel_flag: Option<&Arc<AtomicBool>>,
+    trace_every: Option<usize>,
+    check_hamiltonian: bool,
+    shortest_path: bool,
+    max_depth: usize,
+    search_strategy: Option<&str>,
+    lenient: bool,
+    cycle: bool,
+    deterministic: bool,
+    component_timeout_ms: Option<u64>,
+    neighbor_ordering: NeighborOrdering,
+    cache_key: Option<u64>,
+) -> PyResult<CachedResults> {
+    let total_start = Instant::now();
+
+    if let Some(key) = cache_key {
+        if let Some(cached) = solve_cache().lock().unwrap().get(&key).cloned() {
+            return Ok(cached);
+        }
+    }
+
+    let maze_data: MazeData = serde_json::from_str(&data_str)
+        .map_err(|e| PyErr::new::<PyValueError, _>(format!("JSON error: {}", e)))?;
+
+    // In strict mode, any schema error (payload-level or per-component)
+    // fails the whole solve. In lenient mode, only payload-level errors
+    // are fatal; a component with its own schema errors is skipped and
+    // reported back as a warning instead of blocking its siblings.
+    let component_errors = if lenient {
+        let schema_errors = crate::maze_schema::schema_version_errors(&maze_data);
+        if !schema_errors.is_empty() {
+            return Err(PyErr::new::<PyValueError, _>(schema_errors.join("; ")));
+        }
+        Some(crate::maze_schema::validate_per_component(&maze_data))
+    } else {
+        if let Err(errors) = crate::maze_schema::validate(&maze_data) {
+            return Err(PyErr::new::<PyValueError, _>(errors.join("; ")));
+        }
+        None
+    };
+
+    println!("SOLVING: {} components", maze_data.components.len());
+
+    // Process each component in parallel and collect results
+    let results: CachedResults = maze_data.components.par_iter().enumerate()
+        .map(|(index, component)| {
+            if let Some(errors) = component_errors.as_ref().map(|e| &e[index]) {
+                if !errors.is_empty() {
+                    return Ok((Vec::new(), Vec::new(), None, None, Some(errors.join("; ")), false, 0));
+                }
+            }
+            solve_component(
+                &maze_data, index, component, cancel_flag, trace_every,
+                check_hamiltonian, shortest_path, max_depth, search_strategy, cycle, deterministic,
+                component_timeout_ms, neighbor_ordering,
+            ).map(|(cells, trace_names, hamiltonian, strategy, partial, elapsed_ms)| {
+                (cells, trace_names, hamiltonian, strategy, None, partial, elapsed_ms)
+            })
+        })
+        .collect::<Result<CachedResults, SolverError>>()?;
+
+    println!("TOTAL TIME: {:?}", total_start.elapsed());
+
+    // A partial (timed-out) result reflects this call's own budget, not a
+    // durable answer — caching it would silently hand a later caller asking
+    // for a bigger budget the same truncated path forever.
+    if let Some(key) = cache_key {
+        if !results.iter().any(|(_, _, _, _, _, partial, _)| *partial) {
+            solve_cache().lock().unwrap().insert(key, results.clone());
+        }
+    }
+
+    Ok(results)
+}
+
+/// Shapes a completed `solve_maze_core` result into the same `PyObject`
+/// `process_and_solve_maze` has always returned: a plain `Vec<Vec<String>>`
+/// (or, with `compact_indices`, a `(name_table, Vec<Vec<u32>>)` pair) when
+/// no extra metadata was requested, otherwise one dict per component.
+/// `compact_indices` has no defined shape in the dict-per-component case, so
+/// combining it with any other output flag is a `PyValueError`, not a
+/// silently ignored flag.
+#[allow(clippy::too_many_arguments)]
+fn build_maze_result(
+    py: Python,
+    results: CachedResults,
+    simplify_waypoints: bool,
+    trace_requested: bool,
+    check_hamiltonian: bool,
+    shortest_path: bool,
+    lenient: bool,
+    cycle: bool,
+    timing_requested: bool,
+    compact_indices: bool,
+) -> PyResult<PyObject> {
+    if !simplify_waypoints && !trace_requested && !check_hamiltonian && !shortest_path && !lenient && !cycle && !timing_requested {
+        if compact_indices {
+            // Interns each path node name into a single shared table instead
+            // of repeating long id strings once per occurrence, which is
+            // where the memory/deserialization cost actually comes from on
+            // large solutions: `name_table[i]` is the name for index `i`,
+            // and `indices[component][step]` looks it up.
+            let mut name_table: Vec<String> = Vec::new();
+            let mut index_of: HashMap<String, u32> = HashMap::new();
+            let indices: Vec<Vec<u32>> = results
+                .into_iter()
+                .map(|(cells, _, _, _, _, _, _)| {
+                    cells
+                        .into_iter()
+                        .map(|name| {
+                            *index_of.entry(name.clone()).or_insert_with(|| {
+                                let id = name_table.len() as u32;
+                                name_table.push(name);
+                                id
+                            })
+                        })
+                        .collect()
+                })
+                .collect();
+            return Ok((name_table, indices).into_pyobject(py)?.into_any().unbind());
+        }
+        let cells: Vec<Vec<String>> = results.into_iter().map(|(cells, _, _, _, _, _, _)| cells).collect();
+        return Ok(cells.into_pyobject(py)?.into_any().unbind());
+    }
+
+    // `compact_indices` only has a defined shape in the flat output above —
+    // the per-component dict shape below doesn't have a name-table variant.
+    // Reject the combination instead of silently returning the verbose shape
+    // and leaving the caller to discover their flag was ignored.
+    if compact_indices {
+        return Err(PyErr::new::<PyValueError, _>(
+            "compact_indices is only supported when no other output mode (simplify_waypoints, trace_every, check_hamiltonian, shortest_path, lenient, cycle, component_timeout_ms) is requested",
+        ));
+    }
+
+    let components: Vec<PyObject> = results
+        .iter()
+        .map(|(cells, trace_names, hamiltonian, strategy, warning, partial, elapsed_ms)| {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("cells", cells).unwrap();
+            if simplify_waypoints {
+                let waypoints = self::simplify_waypoints(cells);
+                let waypoint_dicts: Vec<PyObject> = waypoints
+                    .iter()
+                    .map(|w| waypoint_to_dict(py, w))
+                    .collect();
+                dict.set_item("waypoints", waypoint_dicts).unwrap();
+            }
+            if trace_requested {
+                dict.set_item("trace", trace_names).unwrap();
+            }
+            if check_hamiltonian {
+                dict.set_item("is_hamiltonian", hamiltonian).unwrap();
+            }
+            if shortest_path {
+                dict.set_item("strategy", strategy).unwrap();
+            }
+            if cycle {
+                dict.set_item("is_cycle", true).unwrap();
+            }
+            if timing_requested {
+                dict.set_item("partial", partial).unwrap();
+                dict.set_item("elapsed_ms", elapsed_ms).unwrap();
+            }
+            if let Some(warning) = warning {
+                dict.set_item("warning", warning).unwrap();
+            }
+            dict.into_any().unbind()
+        })
+        .collect();
+
+    Ok(components.into_pyobject(py)?.into_any().unbind())
+}
+
+#[pyfunction]
+#[pyo3(signature = (data, simplify_waypoints=false, cancel=None, trace_every=None, check_hamiltonian=false, use_cache=true, shortest_path=false, max_path_depth=None, search_strategy=None, lenient=false, cycle=false, deterministic=false, component_timeout_ms=None, compact_indices=false, neighbor_ordering=None, neighbor_ordering_seed=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn process_and_solve_maze(
+    py: Python,
+    data: PyObject,
+    simplify_waypoints: bool,
+    cancel: Option<CancelHandle>,
+    trace_every: Option<usize>,
+    check_hamiltonian: bool,
+    use_cache: bool,
+    shortest_path: bool,
+    max_path_depth: Option<usize>,
+    search_strategy: Option<String>,
+    lenient: bool,
+    cycle: bool,
+    deterministic: bool,
+    component_timeout_ms: Option<u64>,
+    compact_indices: bool,
+    neighbor_ordering: Option<String>,
+    neighbor_ordering_seed: Option<u64>,
+) -> PyResult<PyObject> {
+    let data_str = data.extract::<String>(py)?;
+    let cancel_flag = cancel.map(|c| c.flag);
+    // Bounds the explicit-stack search's path length so a pathological
+    // component can't grow the stack without limit; defaults to the largest
+    // path any component can have.
+    let max_depth = max_path_depth.unwrap_or(MAX_NODE_COUNT).min(MAX_NODE_COUNT);
+    let cache_key = use_cache.then(|| solve_cache_key(&data_str, trace_every, check_hamiltonian, shortest_path, search_strategy.as_deref(), lenient, cycle, deterministic, neighbor_ordering.as_deref(), neighbor_ordering_seed));
+    let ordering = neighbor_ordering_by_name(neighbor_ordering.as_deref(), neighbor_ordering_seed);
+
+    let results = py.allow_threads(|| {
+        solve_maze_core(
+            data_str, cancel_flag.as_ref(), trace_every, check_hamiltonian, shortest_path, max_depth,
+            search_strategy.as_deref(), lenient, cycle, deterministic, component_timeout_ms, ordering, cache_key,
+        )
+    })?;
+
+    let trace_requested = trace_every.is_some_and(|n| n > 0);
+    let timing_requested = component_timeout_ms.is_some();
+    build_maze_result(py, results, simplify_waypoints, trace_requested, check_hamiltonian, shortest_path, lenient, cycle, timing_requested, compact_indices)
+}
+
+/// A small callable pyclass scheduled onto the asyncio loop via
+/// `call_soon_threadsafe` once a background solve finishes. Running the
+/// actual `future.set_result`/`set_exception` call on the loop's own thread
+/// (rather than from the rayon worker that computed `value`) is what makes
+/// this safe: checking `future.done()` and resolving it only happens here,
+/// never racing the loop's own bookkeeping the way calling those methods
+/// directly from another thread would.
+#[pyclass]
+struct FutureResolver {
+    future: Py<PyAny>,
+    value: Mutex<Option<PyResult<PyObject>>>,
+}
+
+#[pymethods]
+impl FutureResolver {
+    fn __call__(&self, py: Python) -> PyResult<()> {
+        let future = self.future.bind(py);
+        if future.call_method0("done")?.extract::<bool>()? {
+            return Ok(());
+        }
+        match self.value.lock().unwrap().take() {
+            Some(Ok(value)) => {
+                future.call_method1("set_result", (value,))?;
+            }
+            Some(Err(err)) => {
+                future.call_method1("set_exception", (err.value(py),))?;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+/// Watches the future for cancellation (e.g. the awaiting task being
+/// dropped/cancelled) and flips the same `cancel_flag` the solve's
+/// background thread already checks, so a discarded awaitable stops the
+/// in-flight search instead of letting it run to completion unobserved.
+#[pyclass]
+struct AsyncCancelWatcher {
+    flag: Arc<AtomicBool>,
+}
+
+#[pymethods]
+impl AsyncCancelWatcher {
+    fn __call__(&self, future: &Bound<PyAny>) -> PyResult<()> {
+        if future.call_method0("cancelled")?.extract::<bool>()? {
+            self.flag.store(true, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+/// Async counterpart of `process_and_solve_maze`: instead of blocking the
+/// calling thread for the duration of the search, hands the work to a
+/// `rayon::spawn` worker and returns immediately with an `asyncio.Future`
+/// the caller `await`s. Requires a running event loop (call it from a
+/// coroutine), matching `asyncio.get_running_loop()`'s own requirement.
+///
+/// There's no `pyo3-asyncio`/`pyo3-async-runtimes` dependency here: the
+/// former is pinned to pyo3 0.20 and the latter to pyo3 0.29, and this
+/// crate is on pyo3 0.25 — pulling either in would either fail to build
+/// (duplicate `links = "python"`) or force an unrelated, risky pyo3 major
+/// bump just for this one function. `asyncio.Future` plus
+/// `call_soon_threadsafe` is the same primitive those crates build on, so
+/// hand-rolling it here keeps every other pyo3 call site untouched.
+#[pyfunction]
+#[pyo3(signature = (data, simplify_waypoints=false, cancel=None, trace_every=None, check_hamiltonian=false, use_cache=true, shortest_path=false, max_path_depth=None, search_strategy=None, lenient=false, cycle=false, deterministic=false, component_timeout_ms=None, compact_indices=false, neighbor_ordering=None, neighbor_ordering_seed=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn process_and_solve_maze_async(
+    py: Python,
+    data: PyObject,
+    simplify_waypoints: bool,
+    cancel: Option<CancelHandle>,
+    trace_every: Option<usize>,
+    check_hamiltonian: bool,
+    use_cache: bool,
+    shortest_path: bool,
+    max_path_depth: Option<usize>,
+    search_strategy: Option<String>,
+    lenient: bool,
+    cycle: bool,
+    deterministic: bool,
+    component_timeout_ms: Option<u64>,
+    compact_indices: bool,
+    neighbor_ordering: Option<String>,
+    neighbor_ordering_seed: Option<u64>,
+) -> PyResult<PyObject> {
+    let data_str = data.extract::<String>(py)?;
+    let cancel_flag = cancel.map(|c| c.flag).unwrap_or_default();
+    let max_depth = max_path_depth.unwrap_or(MAX_NODE_COUNT).min(MAX_NODE_COUNT);
+    let cache_key = use_cache.then(|| solve_cache_key(&data_str, trace_every, check_hamiltonian, shortest_path, search_strategy.as_deref(), lenient, cycle, deterministic, neighbor_ordering.as_deref(), neighbor_ordering_seed));
+    let ordering = neighbor_ordering_by_name(neighbor_ordering.as_deref(), neighbor_ordering_seed);
+
+    let asyncio = py.import("asyncio")?;
+    let event_loop = asyncio.call_method0("get_running_loop")?;
+    let future = event_loop.call_method0("create_future")?;
+
+    let watcher = Py::new(py, AsyncCancelWatcher { flag: cancel_flag.clone() })?;
+    future.call_method1("add_done_callback", (watcher,))?;
+
+    let loop_obj: Py<PyAny> = event_loop.clone().unbind();
+    let future_obj: Py<PyAny> = future.clone().unbind();
+    let search_strategy_owned = search_strategy;
+    let trace_requested = trace_every.is_some_and(|n| n > 0);
+    let timing_requested = component_timeout_ms.is_some();
+
+    rayon::spawn(move || {
+        // Unlike the sync `process_and_solve_maze` path, where pyo3 catches a
+        // panic at the FFI boundary and turns it into a Python exception, a
+        // panic in this worker thread would otherwise unwind straight past
+        // `Python::with_gil`/`FutureResolver` and leave the future forever
+        // unresolved — the awaiting coroutine hangs with no exception. Catch
+        // it here and resolve the future with an error instead.
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            solve_maze_core(
+                data_str, Some(&cancel_flag), trace_every, check_hamiltonian, shortest_path, max_depth,
+                search_strategy_owned.as_deref(), lenient, cycle, deterministic, component_timeout_ms, ordering, cache_key,
+            )
+        }))
+        .unwrap_or_else(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "solve panicked".to_string());
+            Err(PyErr::from(SolverError::Panicked(message)))
+        });
+
+        Python::with_gil(|py| {
+            let value = outcome.and_then(|results| {
+                build_maze_result(py, results, simplify_waypoints, trace_requested, check_hamiltonian, shortest_path, lenient, cycle, timing_requested, compact_indices)
+            });
+            let resolver = match Py::new(py, FutureResolver { future: future_obj, value: Mutex::new(Some(value)) }) {
+                Ok(resolver) => resolver,
+                Err(_) => return,
+            };
+            let loop_bound = loop_obj.bind(py);
+            let _ = loop_bound.call_method1("call_soon_threadsafe", (resolver,));
+        });
+    });
+
+    Ok(future.into_any().unbind())
+}
+
+/// Solves many mazes in one call. Callers working through hundreds of small
+/// mazes pay Python<->Rust call overhead (and spin up a fresh rayon
+/// parallel iteration) once per maze with `process_and_solve_maze`; this
+/// flattens every maze's components into a single `par_iter` so they're all
+/// scheduled across one pool invocation instead. Each maze's failure is
+/// isolated to its own slot in the returned list — a bad payload doesn't
+/// take down the batch, it just reports its own `{"error": ...}` dict where
+/// its solved cells would otherwise be.
+#[pyfunction]
+#[pyo3(signature = (payloads, check_hamiltonian=false, shortest_path=false, use_cache=true, max_path_depth=None, search_strategy=None, deterministic=false, component_timeout_ms=None, neighbor_ordering=None, neighbor_ordering_seed=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn process_and_solve_mazes(
+    py: Python,
+    payloads: Vec<String>,
+    check_hamiltonian: bool,
+    shortest_path: bool,
+    use_cache: bool,
+    max_path_depth: Option<usize>,
+    search_strategy: Option<String>,
+    deterministic: bool,
+    component_timeout_ms: Option<u64>,
+    neighbor_ordering: Option<String>,
+    neighbor_ordering_seed: Option<u64>,
+) -> PyResult<PyObject> {
+    let max_depth = max_path_depth.unwrap_or(MAX_NODE_COUNT).min(MAX_NODE_COUNT);
+    let ordering = neighbor_ordering_by_name(neighbor_ordering.as_deref(), neighbor_ordering_seed);
+
+    let cache_keys: Vec<Option<u64>> = payloads
+        .iter()
+        .map(|data_str| use_cache.then(|| solve_cache_key(data_str, None, check_hamiltonian, shortest_path, search_strategy.as_deref(), false, false, deterministic, neighbor_ordering.as_deref(), neighbor_ordering_seed)))
+        .collect();
+
+    let mut maze_results: Vec<Option<Result<CachedResults, String>>> = cache_keys
+        .iter()
+        .map(|key| key.and_then(|k| solve_cache().lock().unwrap().get(&k).cloned()).map(Ok))
+        .collect();
+
+    // Parse and validate every not-yet-cached payload up front, so a bad
+    // payload is reported without ever entering the parallel solve pass.
+    let mut parsed: Vec<Option<MazeData>> = (0..payloads.len()).map(|_| None).collect();
+    for (maze_index, data_str) in payloads.iter().enumerate() {
+        if maze_results[maze_index].is_some() {
+            continue;
+        }
+        match serde_json::from_str::<MazeData>(data_str) {
+            Ok(maze_data) => match crate::maze_schema::validate(&maze_data) {
+                Ok(()) => parsed[maze_index] = Some(maze_data),
+                Err(errors) => maze_results[maze_index] = Some(Err(errors.join("; "))),
+            },
+            Err(e) => maze_results[maze_index] = Some(Err(format!("JSON error: {}", e))),
+        }
+    }
+
+    py.allow_threads(|| {
+        // Flatten every parsed maze's components into one job list, tagged
+        // with (maze_index, component_index), so a single par_iter covers
+        // the whole batch instead of one per maze.
+        type ComponentJob<'a> = (usize, usize, &'a HashMap<String, Vec<String>>);
+        let mut jobs: Vec<ComponentJob> = Vec::new();
+        for (maze_index, maze_data) in parsed.iter().enumerate() {
+            if let Some(maze_data) = maze_data {
+                for (component_index, component) in maze_data.components.iter().enumerate() {
+                    jobs.push((maze_index, component_index, component));
+                }
+            }
+        }
+
+        let job_results: Vec<((usize, usize), Result<_, SolverError>)> = jobs
+            .par_iter()
+            .map(|&(maze_index, component_index, component)| {
+                let maze_data = parsed[maze_index].as_ref().unwrap();
+                let result = solve_component(
+                    maze_data, component_index, component, None, None, check_hamiltonian, shortest_path, max_depth,
+                    search_strategy.as_deref(), false, deterministic, component_timeout_ms,
+                    ordering,
+                ).map(|(cells, trace_names, hamiltonian, strategy, partial, elapsed_ms)| {
+                    (cells, trace_names, hamiltonian, strategy, None, partial, elapsed_ms)
+                });
+                ((maze_index, component_index), result)
+            })
+            .collect();
+
+        let mut grouped: Vec<Vec<Option<Result<_, SolverError>>>> = parsed
+            .iter()
+            .map(|maze_data| maze_data.as_ref().map_or(Vec::new(), |m| (0..m.components.len()).map(|_| None).collect()))
+            .collect();
+        for ((maze_index, component_index), result) in job_results {
+            grouped[maze_index][component_index] = Some(result);
+        }
+
+        for (maze_index, slots) in grouped.into_iter().enumerate() {
+            if slots.is_empty() && parsed[maze_index].is_none() {
+                continue; // already resolved from cache or a parse/validation error
+            }
+
+            let mut components: CachedResults = Vec::with_capacity(slots.len());
+            let mut failure = None;
+            for slot in slots {
+                match slot.expect("every component job was scheduled") {
+                    Ok(item) => components.push(item),
+                    Err(e) => {
+                        failure = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+
+            maze_results[maze_index] = Some(match failure {
+                Some(e) => Err(e),
+                None => {
+                    let any_partial = components.iter().any(|(_, _, _, _, _, partial, _)| *partial);
+                    if let Some(key) = cache_keys[maze_index] {
+                        if !any_partial {
+                            solve_cache().lock().unwrap().insert(key, components.clone());
+                        }
+                    }
+                    Ok(components)
+                }
+            });
+        }
+    });
+
+    let mut entries: Vec<PyObject> = Vec::with_capacity(maze_results.len());
+    for result in maze_results {
+        let entry = match result.expect("every maze was resolved") {
+            Ok(results) => {
+                let cells: Vec<Vec<String>> = results.into_iter().map(|(cells, _, _, _, _, _, _)| cells).collect();
+                cells.into_pyobject(py)?.into_any().unbind()
+            }
+            Err(message) => {
+                let dict = pyo3::types::PyDict::new(py);
+                dict.set_item("error", message).unwrap();
+                dict.into_any().unbind()
+            }
+        };
+        entries.push(entry);
+    }
+
+    Ok(entries.into_pyobject(py)?.into_any().unbind())
+}
+
+// Function to verify a path is valid (no duplicates, all edges exist)
+fn validate_path(graph: &Graph, path: &[NodeId]) -> bool {
+    if path.is_empty() {
+        return true;
+    }
+    
+    // Check for duplicates
+    let mut seen = HashSet::new();
+    for &node in path {
+        if !seen.insert(node) {
+            return false; // Duplicate found
+        }
+    }
+    
+    // Check all edges exist
+    for i in 0..path.len()-1 {
+        let curr = path[i];
+        let next = path[i+1];
+        
+        if !graph.get_neighbors(curr).contains(&next) {
+            return false; // Non-adjacent nodes
+        }
+    }
+    
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_bitset_basic() {
+        let mut visited = NodeBitset::new();
+        assert_eq!(visited.contains(10), false);
+        visited.set(10);
+        assert_eq!(visited.contains(10), true);
+        visited.clear(10);
+        assert_eq!(visited.contains(10), false);
+    }
+    
+    #[test]
+    fn test_node_bitset_multiple() {
+        let mut visited = NodeBitset::new();
+        for i in 0..100 {
+            visited.set(i);
+        }
+        assert_eq!(visited.count(), 100);
+        
+        for i in 0..100 {
+            assert_eq!(visited.contains(i), true);
+        }
+        
+        for i in 100..200 {
+            assert_eq!(visited.contains(i), false);
+        }
+    }
+}
+
+/// Property-based tests over randomly generated connected graphs, checking
+/// invariants the fixed hand-written fixtures above don't exercise: any
+/// shape of connected graph the search might see, not just the checked-in
+/// hex-maze cases.
+#[cfg(test)]
+mod proptest_tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Matches `Graph`'s real adjacency cap in this crate's actual domain
+    /// (a hex cell has at most 6 neighbors) rather than `HeaplessVec`'s raw
+    /// capacity of 8, so generated graphs look like maze components instead
+    /// of triggering `add_edge`'s capacity error.
+    const MAX_DEGREE: usize = 6;
+
+    fn add_edge(adjacency: &mut HashMap<String, Vec<String>>, degree: &mut [usize], a: usize, b: usize) {
+        if a == b || degree[a] >= MAX_DEGREE || degree[b] >= MAX_DEGREE {
+            return;
+        }
+        let (a_str, b_str) = (a.to_string(), b.to_string());
+        if adjacency[&a_str].contains(&b_str) {
+            return;
+        }
+        adjacency.get_mut(&a_str).unwrap().push(b_str.clone());
+        adjacency.get_mut(&b_str).unwrap().push(a_str);
+        degree[a] += 1;
+        degree[b] += 1;
+    }
+
+    /// Builds a random connected graph over `parents.len() + 1` nodes: node
+    /// `i` (for `i >= 1`) attaches to `parents[i - 1] % i`, a random
+    /// earlier node (or, if that node is already at `MAX_DEGREE`, the next
+    /// earlier node with room) — which guarantees a connected, cycle-free
+    /// spanning tree by construction regardless of the random values
+    /// proptest picks — then `extra_edges` adds a handful of random additional edges (each
+    /// skipped if either endpoint is already at `MAX_DEGREE`) so the search
+    /// also sees graphs with cycles, not just trees.
+    fn build_random_graph(parents: &[usize], extra_edges: &[(usize, usize)]) -> Graph {
+        let node_count = parents.len() + 1;
+        let mut adjacency: HashMap<String, Vec<String>> = (0..node_count).map(|i| (i.to_string(), Vec::new())).collect();
+        let mut degree = vec![0usize; node_count];
+
+        for (i, &raw_parent) in parents.iter().enumerate() {
+            let child = i + 1;
+            // A tree over `child` nodes has average degree below 2, so some
+            // earlier node always has room for the new edge.
+            let mut parent = raw_parent % child;
+            while degree[parent] >= MAX_DEGREE {
+                parent = (parent + 1) % child;
+            }
+            add_edge(&mut adjacency, &mut degree, child, parent);
+        }
+        for &(raw_a, raw_b) in extra_edges {
+            add_edge(&mut adjacency, &mut degree, raw_a % node_count, raw_b % node_count);
+        }
+
+        build_graph_from_adjacency(&adjacency).0
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// The exact solver's path must always be simple (`validate_path`)
+        /// and at least as long as the `log_maxdegree(n)` lower bound any
+        /// bounded-degree connected graph guarantees for its longest path.
+        #[test]
+        fn exact_search_path_is_simple_and_meets_length_baseline(
+            parents in prop::collection::vec(any::<usize>(), 1..12),
+            extra_edges in prop::collection::vec((any::<usize>(), any::<usize>()), 0..6),
+        ) {
+            let graph = build_random_graph(&parents, &extra_edges);
+            let node_count = graph.node_count();
+            let max_degree = graph.nodes().iter().map(|&n| graph.get_neighbors(n).len()).max().unwrap_or(1).max(2);
+
+            let (path, depth_exceeded) = ExactBacktracking.search(&graph, None, &[], None, None, 0, MAX_NODE_COUNT, true);
+
+            prop_assert!(!depth_exceeded);
+            prop_assert!(validate_path(&graph, &path));
+
+            let baseline = ((node_count as f64).ln() / (max_degree as f64).ln()).ceil().max(1.0) as usize;
+            prop_assert!(
+                path.len() >= baseline,
+                "path of {} nodes below log_{}({}) baseline of {} nodes",
+                path.len(), max_degree, node_count, baseline
+            );
+        }
+
+        /// `BeamSearch` trades exactness for speed, so it can never find a
+        /// *longer* simple path than the exhaustive exact solver on the
+        /// same graph — only an equal or shorter one.
+        #[test]
+        fn beam_search_never_beats_exact_backtracking(
+            parents in prop::collection::vec(any::<usize>(), 1..9),
+            extra_edges in prop::collection::vec((any::<usize>(), any::<usize>()), 0..4),
+        ) {
+            let graph = build_random_graph(&parents, &extra_edges);
+
+            let (exact_path, _) = ExactBacktracking.search(&graph, None, &[], None, None, 0, MAX_NODE_COUNT, true);
+            let (beam_path, _) = BeamSearch { width: 4 }.search(&graph, None, &[], None, None, 0, MAX_NODE_COUNT, true);
+
+            prop_assert!(validate_path(&graph, &beam_path));
+            prop_assert!(beam_path.len() <= exact_path.len());
         }
     }
 }
\ No newline at end of file